@@ -124,7 +124,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 }
                 _ => {}
             },
-            WindowEvent::RedrawRequested => {
+            WindowEvent::RedrawRequested { .. } => {
                 #[cfg(not(web_platform))]
                 fill::fill_window(&window);
             }
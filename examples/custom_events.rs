@@ -47,7 +47,7 @@ fn main() -> Result<(), impl std::error::Error> {
             ..
         } => elwt.exit(),
         Event::WindowEvent {
-            event: WindowEvent::RedrawRequested,
+            event: WindowEvent::RedrawRequested { .. },
             ..
         } => {
             fill::fill_window(&window);
@@ -37,7 +37,7 @@ fn main() -> Result<(), impl std::error::Error> {
             }
             Event::WindowEvent { event, window_id } if window_id == window.id() => match event {
                 WindowEvent::CloseRequested => elwt.exit(),
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     // Notify the windowing system that we'll be presenting to the window.
                     window.pre_present_notify();
                     fill::fill_window(&window);
@@ -76,7 +76,7 @@ fn main() -> Result<(), impl std::error::Error> {
                         _ => (),
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
@@ -84,7 +84,7 @@ fn main() -> Result<(), impl std::error::Error> {
                         println!("\nIME purpose: {ime_purpose:?}\n");
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
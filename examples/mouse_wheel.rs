@@ -55,7 +55,7 @@ In other words, the deltas indicate the direction in which to move the content (
                         window.set_outer_position(pos)
                     }
                 },
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
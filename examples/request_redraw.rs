@@ -31,7 +31,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 } => {
                     window.request_redraw();
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     println!("\nredrawing!\n");
                     fill::fill_window(&window);
                 }
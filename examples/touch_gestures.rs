@@ -52,7 +52,7 @@ fn main() -> Result<(), impl std::error::Error> {
                         println!("Rotated clockwise {delta:.5} (now: {rotated:.5})");
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
@@ -28,7 +28,7 @@ fn main() -> Result<(), impl std::error::Error> {
         if let Event::WindowEvent { event, .. } = event {
             match event {
                 WindowEvent::CloseRequested => elwt.exit(),
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
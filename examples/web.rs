@@ -118,7 +118,7 @@ mod wasm {
         // As a bonus its also kind of handy on desktop.
         let event = match event {
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { .. },
                 ..
             } => None,
             Event::WindowEvent { event, .. } => Some(format!("{event:?}")),
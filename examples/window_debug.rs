@@ -126,7 +126,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     _ => (),
                 },
                 WindowEvent::CloseRequested if window_id == window.id() => elwt.exit(),
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
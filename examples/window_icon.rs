@@ -40,7 +40,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 WindowEvent::DroppedFile(path) => {
                     window.set_window_icon(Some(load_icon(&path)));
                 }
-                WindowEvent::RedrawRequested => fill::fill_window(&window),
+                WindowEvent::RedrawRequested { .. } => fill::fill_window(&window),
                 _ => (),
             }
         }
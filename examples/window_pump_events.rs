@@ -47,7 +47,7 @@ fn main() -> std::process::ExitCode {
                     window.request_redraw();
                 }
                 Event::WindowEvent {
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { .. },
                     ..
                 } => {
                     fill::fill_window(&window);
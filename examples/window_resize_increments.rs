@@ -40,7 +40,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 debug!("Had increments: {}", new_increments.is_none());
                 window.set_resize_increments(new_increments);
             }
-            WindowEvent::RedrawRequested => {
+            WindowEvent::RedrawRequested { .. } => {
                 fill::fill_window(&window);
             }
             _ => (),
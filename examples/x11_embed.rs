@@ -42,7 +42,7 @@ mod imple {
                     window.request_redraw();
                 }
                 Event::WindowEvent {
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { .. },
                     ..
                 } => {
                     // Notify the windowing system that we'll be presenting to the window.
@@ -32,8 +32,10 @@
 //!
 //! [`EventLoop::run(...)`]: crate::event_loop::EventLoop::run
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::ops::Range;
 use std::path::PathBuf;
 use std::sync::{Mutex, Weak};
+use std::time::Duration;
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
@@ -48,10 +50,11 @@ use crate::error::ExternalError;
 use crate::window::Window;
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
-    event_loop::AsyncRequestSerial,
-    keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState},
+    event_loop::{AsyncRequestSerial, SystemSettings},
+    keyboard::{self, LockState, ModifiersKeyState, ModifiersKeys, ModifiersState},
+    monitor::MonitorHandle,
     platform_impl,
-    window::{ActivationToken, Theme, WindowId},
+    window::{AccentColor, ActivationToken, Theme, WindowId},
 };
 
 /// Describes a generic event.
@@ -228,6 +231,46 @@ pub enum Event<T: 'static> {
     /// gets emitted. You generally want to treat this as a "do on quit" event.
     LoopExiting,
 
+    /// Emitted when a monitor was connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Supported.
+    /// - **Others:** Unsupported.
+    MonitorAdded(MonitorHandle),
+
+    /// Emitted when a monitor was disconnected.
+    ///
+    /// The handle can no longer be used to query the monitor, except for comparing it with the
+    /// handles obtained before.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Supported.
+    /// - **Others:** Unsupported.
+    MonitorRemoved(MonitorHandle),
+
+    /// Emitted when the configuration of a monitor, like its video mode, position or scale factor,
+    /// has changed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Supported.
+    /// - **Others:** Unsupported.
+    MonitorChanged(MonitorHandle),
+
+    /// Emitted when the system settings have changed.
+    ///
+    /// See [`EventLoopWindowTarget::system_settings`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland / Windows:** Supported.
+    /// - **Others:** Unsupported.
+    ///
+    /// [`EventLoopWindowTarget::system_settings`]: crate::event_loop::EventLoopWindowTarget::system_settings
+    SystemSettingsChanged(SystemSettings),
+
     /// Emitted when the application has received a memory warning.
     ///
     /// ## Platform-specific
@@ -268,6 +311,10 @@ impl<T> Event<T> {
             LoopExiting => Ok(LoopExiting),
             Suspended => Ok(Suspended),
             Resumed => Ok(Resumed),
+            MonitorAdded(monitor) => Ok(MonitorAdded(monitor)),
+            MonitorRemoved(monitor) => Ok(MonitorRemoved(monitor)),
+            MonitorChanged(monitor) => Ok(MonitorChanged(monitor)),
+            SystemSettingsChanged(settings) => Ok(SystemSettingsChanged(settings)),
             MemoryWarning => Ok(MemoryWarning),
         }
     }
@@ -320,6 +367,17 @@ pub enum WindowEvent {
         token: ActivationToken,
     },
 
+    /// The window was exported and the handle could now be passed to other processes.
+    ///
+    #[cfg_attr(not(wayland_platform), allow(rustdoc::broken_intra_doc_links))]
+    /// Delivered in response to [`export_handle`].
+    ///
+    /// [`export_handle`]: crate::platform::wayland::WindowExtWayland::export_handle
+    ExportHandleDone {
+        serial: AsyncRequestSerial,
+        handle: String,
+    },
+
     /// The size of the window has changed. Contains the client area's new dimensions.
     Resized(PhysicalSize<u32>),
 
@@ -336,6 +394,14 @@ pub enum WindowEvent {
     /// The window has been destroyed.
     Destroyed,
 
+    /// The popup has been dismissed by the windowing system, for example because the user clicked
+    /// outside of it. The window should be dropped.
+    ///
+    /// Only sent to the windows built with [`WindowBuilder::with_popup`].
+    ///
+    /// [`WindowBuilder::with_popup`]: crate::window::WindowBuilder::with_popup
+    PopupDone,
+
     /// A file has been dropped into the window.
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
@@ -384,6 +450,15 @@ pub enum WindowEvent {
     /// The keyboard modifiers have changed.
     ModifiersChanged(Modifiers),
 
+    /// The keyboard layouts or the active one have changed.
+    ///
+    /// This is sent to the window with the keyboard focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only emitted on **X11** and **Wayland**.
+    KeyboardLayoutChanged(keyboard::KeyboardLayoutInfo),
+
     /// An event from an input method.
     ///
     /// **Note:** You have to explicitly enable this event using [`Window::set_ime_allowed`].
@@ -393,6 +468,34 @@ pub enum WindowEvent {
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     Ime(Ime),
 
+    /// The progress of a dead key or `Compose` key sequence handled by winit.
+    ///
+    /// It's sent when a key starts or continues a sequence, and when the sequence is composed
+    /// or cancelled. The composed text is delivered with the [`KeyboardInput`] event of the last
+    /// key, so this is only for showing feedback while composing.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only emitted on **X11** and **Wayland**, unless disabled with
+    ///   [`EventLoopBuilderExtUnix::with_xkb_compose`].
+    ///
+    /// [`KeyboardInput`]: Self::KeyboardInput
+    /// [`EventLoopBuilderExtUnix::with_xkb_compose`]: crate::platform::xkb::EventLoopBuilderExtUnix::with_xkb_compose
+    #[cfg_attr(
+        not(any(x11_platform, wayland_platform)),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
+    ComposeState {
+        /// The text of the keys of the sequence so far, like `´` after the acute dead key.
+        ///
+        /// Only set when the [`status`](Self::ComposeState::status) is
+        /// [`ComposeStatus::Composing`]. It's empty when the keys have no text, like the
+        /// `Compose` key.
+        pending: Option<String>,
+
+        status: ComposeStatus,
+    },
+
     /// The cursor has moved on the window.
     ///
     /// ## Platform-specific
@@ -577,9 +680,22 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
+    /// - **X11:** Only sent when the theme isn't set with [`Window::set_theme`].
+    /// - **Wayland:** Only sent when the theme isn't set with [`Window::set_theme`], requires the
+    ///   XDG desktop portal on the session bus and the `zbus` feature.
+    /// - **iOS / Android / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
+    /// The accent color of the system has changed.
+    ///
+    /// See [`Window::accent_color`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the XDG desktop portal on the session bus and the `zbus` feature.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported.
+    AccentColorChanged(AccentColor),
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
@@ -617,7 +733,22 @@ pub enum WindowEvent {
     ///
     /// Winit will aggregate duplicate redraw requests into a single event, to
     /// help avoid duplicating rendering work.
-    RedrawRequested,
+    RedrawRequested {
+        /// The presentation timestamp of the frame this redraw is aligned with.
+        ///
+        /// This is only provided when the redraw was scheduled by a frame callback from the
+        /// windowing system, for example after [`Window::request_frame`]. The timestamp has an
+        /// undefined base, only the difference between two timestamps is meaningful.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Wayland:** The time of the `wl_surface.frame` callback, with millisecond
+        ///   granularity.
+        /// - **X11:** The time of the `_NET_WM_FRAME_DRAWN` message, with microsecond
+        ///   granularity.
+        /// - **Windows / macOS / iOS / Android / Web / Orbital:** Always `None`.
+        frame_time: Option<Duration>,
+    },
 }
 
 /// Identifier of an input device.
@@ -686,8 +817,20 @@ pub enum DeviceEvent {
     },
 
     Key(RawKeyEvent),
+
+    /// A global hotkey was pressed, even when none of the windows has the focus.
+    ///
+    #[cfg_attr(not(x11_platform), allow(rustdoc::broken_intra_doc_links))]
+    /// The hotkeys are registered with [`register_global_hotkey`].
+    ///
+    /// [`register_global_hotkey`]: crate::platform::x11::EventLoopWindowTargetExtX11::register_global_hotkey
+    GlobalHotkey(HotkeyId),
 }
 
+/// Identifier of a global hotkey, see [`DeviceEvent::GlobalHotkey`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HotkeyId(pub(crate) u32);
+
 /// Describes a keyboard input as a raw device event.
 ///
 /// Note that holding down a key may produce repeated `RawKeyEvent`s. The
@@ -805,6 +948,19 @@ pub struct KeyEvent {
     /// this event is the result of one of those repeats.
     pub repeat: bool,
 
+    /// Whether the event was generated by winit rather than reported by the system.
+    ///
+    /// This is the case for the key repeats on Wayland, which winit generates with the
+    /// [`RepeatInfo`] of the compositor, unless they were disabled with
+    /// [`EventLoopBuilderExtWayland::with_synthetic_key_repeat`].
+    ///
+    /// This differs from the `is_synthetic` of [`WindowEvent::KeyboardInput`], which marks the
+    /// key presses and releases synthesized when the window gains or loses the focus.
+    ///
+    /// [`RepeatInfo`]: crate::keyboard::RepeatInfo
+    /// [`EventLoopBuilderExtWayland::with_synthetic_key_repeat`]: crate::platform::wayland::EventLoopBuilderExtWayland::with_synthetic_key_repeat
+    pub is_generated: bool,
+
     /// Platform-specific key event information.
     ///
     /// On Windows, Linux and macOS, this type contains the key without modifiers and the text with all
@@ -819,6 +975,8 @@ pub struct KeyEvent {
 pub struct Modifiers {
     pub(crate) state: ModifiersState,
 
+    pub(crate) lock_state: LockState,
+
     // NOTE: Currently pressed modifiers keys.
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
@@ -831,6 +989,19 @@ impl Modifiers {
         self.state
     }
 
+    /// The state of the lock keys.
+    ///
+    /// It's reported when the window gains focus, so it's known before any key is pressed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** The scroll lock is only reported on X11, from the indicator of the
+    ///   server.
+    /// - **Windows / macOS / Web / iOS / Android / Orbital:** Unsupported, always empty.
+    pub fn lock_state(&self) -> LockState {
+        self.lock_state
+    }
+
     /// The state of the left shift key.
     pub fn lshift_state(&self) -> ModifiersKeyState {
         self.mod_state(ModifiersKeys::LSHIFT)
@@ -884,6 +1055,7 @@ impl From<ModifiersState> for Modifiers {
     fn from(value: ModifiersState) -> Self {
         Self {
             state: value,
+            lock_state: Default::default(),
             pressed_mods: Default::default(),
         }
     }
@@ -901,9 +1073,9 @@ impl From<ModifiersState> for Modifiers {
 /// the character you want to apply the accent to. In this case, some platforms will generate the following event sequence:
 /// ```ignore
 /// // Press "`" key
-/// Ime::Preedit("`", Some((0, 0)))
+/// Ime::Preedit("`", Some((0, 0)), vec![])
 /// // Press "E" key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, vec![]) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("é")
 /// ```
 ///
@@ -914,15 +1086,15 @@ impl From<ModifiersState> for Modifiers {
 /// sequence could be obtained:
 /// ```ignore
 /// // Press "A" key
-/// Ime::Preedit("a", Some((1, 1)))
+/// Ime::Preedit("a", Some((1, 1)), vec![])
 /// // Press "B" key
-/// Ime::Preedit("a b", Some((3, 3)))
+/// Ime::Preedit("a b", Some((3, 3)), vec![])
 /// // Press left arrow key
-/// Ime::Preedit("a b", Some((1, 1)))
+/// Ime::Preedit("a b", Some((1, 1)), vec![])
 /// // Press space key
-/// Ime::Preedit("啊b", Some((3, 3)))
+/// Ime::Preedit("啊b", Some((3, 3)), vec![])
 /// // Press space key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, vec![]) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("啊不")
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -937,18 +1109,42 @@ pub enum Ime {
 
     /// Notifies when a new composing text should be set at the cursor position.
     ///
-    /// The value represents a pair of the preedit string and the cursor begin position and end
-    /// position. When it's `None`, the cursor should be hidden. When `String` is an empty string
-    /// this indicates that preedit was cleared.
+    /// The value represents the preedit string, the cursor begin position and end position, and
+    /// the styles of the segments of the preedit. When the cursor is `None`, it should be hidden.
+    /// When `String` is an empty string this indicates that preedit was cleared.
+    ///
+    /// The cursor position and the ranges of the segments are byte-wise indexed. The segments
+    /// without a style aren't listed.
+    ///
+    /// ## Platform-specific
     ///
-    /// The cursor position is byte-wise indexed.
-    Preedit(String, Option<(usize, usize)>),
+    /// - The segments are only reported on **X11**.
+    Preedit(
+        String,
+        Option<(usize, usize)>,
+        Vec<(Range<usize>, PreeditStyle)>,
+    ),
 
     /// Notifies when text should be inserted into the editor widget.
     ///
     /// Right before this event winit will send empty [`Self::Preedit`] event.
     Commit(String),
 
+    /// Notifies when the text around the cursor should be deleted.
+    ///
+    /// The lengths are in bytes of the text last set with [`Window::set_ime_surrounding_text`],
+    /// before and after the cursor, or the selection when there's one. The preedit is cleared
+    /// before this event, and the following [`Commit`](Self::Commit) should be inserted at the
+    /// position of the deleted text.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only emitted on **Wayland**.
+    DeleteSurrounding {
+        before_bytes: usize,
+        after_bytes: usize,
+    },
+
     /// Notifies when the IME was disabled.
     ///
     /// After receiving this event you won't get any more [`Preedit`](Self::Preedit) or
@@ -958,6 +1154,40 @@ pub enum Ime {
     Disabled,
 }
 
+/// The style of a segment of the preedit text, see [`Ime::Preedit`].
+///
+/// The input methods use it to mark the state of the conversion, for example the clause being
+/// converted is selected while the rest of the preedit is underlined.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PreeditStyle {
+    /// The segment should be underlined.
+    pub underline: bool,
+
+    /// The segment should be highlighted.
+    pub highlight: bool,
+
+    /// The segment is the selected clause, usually drawn with inverted colors.
+    pub selected: bool,
+}
+
+/// The status of a compose sequence, see [`WindowEvent::ComposeState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ComposeStatus {
+    /// The sequence was started or continued.
+    Composing,
+
+    /// The sequence was completed, the text is in the [`KeyboardInput`] event of the last key.
+    ///
+    /// [`KeyboardInput`]: WindowEvent::KeyboardInput
+    Composed,
+
+    /// The sequence didn't match any compose sequence and was dropped.
+    Cancelled,
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1190,7 +1420,12 @@ mod tests {
 
             #[allow(deprecated)]
             {
-                use crate::event::{Event::*, Ime::Enabled, WindowEvent::*};
+                use crate::event::{
+                    ComposeStatus,
+                    Event::*,
+                    Ime::{DeleteSurrounding, Enabled},
+                    WindowEvent::*,
+                };
                 use crate::window::WindowId;
 
                 // Mainline events.
@@ -1201,6 +1436,7 @@ mod tests {
                 x(LoopExiting);
                 x(Suspended);
                 x(Resumed);
+                x(SystemSettingsChanged(Default::default()));
 
                 // Window events.
                 let with_window_event = |wev| {
@@ -1212,6 +1448,7 @@ mod tests {
 
                 with_window_event(CloseRequested);
                 with_window_event(Destroyed);
+                with_window_event(PopupDone);
                 with_window_event(Focused(true));
                 with_window_event(Moved((0, 0).into()));
                 with_window_event(Resized((0, 0).into()));
@@ -1219,11 +1456,20 @@ mod tests {
                 with_window_event(HoveredFile("x.txt".into()));
                 with_window_event(HoveredFileCancelled);
                 with_window_event(Ime(Enabled));
+                with_window_event(Ime(DeleteSurrounding {
+                    before_bytes: 0,
+                    after_bytes: 0,
+                }));
                 with_window_event(CursorMoved {
                     device_id: did,
                     position: (0, 0).into(),
                 });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
+                with_window_event(KeyboardLayoutChanged(Default::default()));
+                with_window_event(ComposeState {
+                    pending: Some("´".to_owned()),
+                    status: ComposeStatus::Composing,
+                });
                 with_window_event(CursorEntered { device_id: did });
                 with_window_event(CursorLeft { device_id: did });
                 with_window_event(MouseWheel {
@@ -1265,7 +1511,15 @@ mod tests {
                     force: Some(event::Force::Normalized(0.0)),
                 }));
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
+                with_window_event(AccentColorChanged(crate::window::AccentColor {
+                    red: 0,
+                    green: 0,
+                    blue: 0,
+                }));
                 with_window_event(Occluded(true));
+                with_window_event(RedrawRequested {
+                    frame_time: Some(std::time::Duration::from_millis(16)),
+                });
             }
 
             #[allow(deprecated)]
@@ -19,7 +19,8 @@ use std::time::{Duration, Instant};
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
-use crate::error::EventLoopError;
+use crate::error::{EventLoopError, ExternalError};
+use crate::keyboard::KeyboardLayoutInfo;
 use crate::{event::Event, monitor::MonitorHandle, platform_impl};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
@@ -329,6 +330,53 @@ impl EventLoopWindowTarget {
             .map(|inner| MonitorHandle { inner })
     }
 
+    /// Returns the current system settings, like the double click interval or the key repeat
+    /// rate.
+    ///
+    /// The changes are reported with [`Event::SystemSettingsChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The settings come from the XSETTINGS and the XKB repeat controls.
+    /// - **Wayland:** Only the key repeat, reported by the compositor for the keyboard.
+    /// - **macOS:** The double click interval and the key repeat. The changes aren't reported.
+    /// - **Web / iOS / Android / Orbital:** Unsupported.
+    #[inline]
+    pub fn system_settings(&self) -> SystemSettings {
+        self.p.system_settings()
+    }
+
+    /// Returns the keyboard layouts and the active one.
+    ///
+    /// The changes are reported with [`WindowEvent::KeyboardLayoutChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The short names and the variants of the layouts come from the XKB rules names
+    ///   set on the root window.
+    /// - **Wayland:** The layouts of the keymap of the first keyboard, without the short names and
+    ///   the variants.
+    /// - **Windows / macOS / Web / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::KeyboardLayoutChanged`]: crate::event::WindowEvent::KeyboardLayoutChanged
+    #[inline]
+    pub fn keyboard_layouts(&self) -> KeyboardLayoutInfo {
+        self.p.keyboard_layouts()
+    }
+
+    /// Requests to switch to the keyboard layout at `index` of
+    /// [`KeyboardLayoutInfo::layouts`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Locks the XKB group of the core keyboard.
+    /// - **Wayland / Windows / macOS / Web / iOS / Android / Orbital:** Always returns an
+    ///   [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn set_keyboard_layout(&self, index: usize) -> Result<(), ExternalError> {
+        self.p.set_keyboard_layout(index)
+    }
+
     /// Change if or when [`DeviceEvent`]s are captured.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
@@ -500,6 +548,41 @@ pub enum DeviceEvents {
     Never,
 }
 
+/// The system settings that affect how the applications respond to the user, see
+/// [`EventLoopWindowTarget::system_settings`].
+///
+/// The settings the platform doesn't report are `None`.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SystemSettings {
+    /// The maximum time between the clicks of a double click.
+    pub double_click_interval: Option<Duration>,
+
+    /// The maximum distance between the clicks of a double click, in physical pixels.
+    pub double_click_distance: Option<u32>,
+
+    /// The time a key is held before it starts repeating.
+    ///
+    /// `None` when the keys don't repeat.
+    pub key_repeat_delay: Option<Duration>,
+
+    /// The time between the repeats of a held key.
+    ///
+    /// `None` when the keys don't repeat.
+    pub key_repeat_interval: Option<Duration>,
+
+    /// The time between the blinks of the text caret.
+    ///
+    /// `Duration::ZERO` when the caret doesn't blink.
+    pub caret_blink_interval: Option<Duration>,
+
+    /// The number of lines scrolled by a notch of the mouse wheel.
+    pub scroll_lines: Option<u32>,
+
+    /// Whether the user asked for the animations to be reduced.
+    pub reduce_motion: Option<bool>,
+}
+
 /// A unique identifier of the winit's async request.
 ///
 /// This could be used to identify the async request once it's done
@@ -69,11 +69,15 @@
 //
 // --------- END OF W3C SHORT NOTICE ---------------------------------------------------------------
 
+use std::time::Duration;
+
 use bitflags::bitflags;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 pub use smol_str::SmolStr;
 
+use crate::platform_impl;
+
 /// Contains the platform-native physical key identifier
 ///
 /// The exact values vary from platform to platform (which is part of why this is a per-platform
@@ -1681,6 +1685,69 @@ pub enum KeyLocation {
     Numpad,
 }
 
+/// How the held keys are repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RepeatInfo {
+    /// The held keys are repeated after the `delay`, every `interval`.
+    Repeat {
+        /// The time a key is held before it starts repeating.
+        delay: Duration,
+
+        /// The time between the repeats.
+        interval: Duration,
+    },
+
+    /// The keys aren't repeated.
+    Disabled,
+}
+
+/// The keyboard layouts of the system, see [`EventLoopWindowTarget::keyboard_layouts`].
+///
+/// [`EventLoopWindowTarget::keyboard_layouts`]: crate::event_loop::EventLoopWindowTarget::keyboard_layouts
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfo {
+    /// The layouts, in the order of their indices.
+    pub layouts: Vec<KeyboardLayout>,
+
+    /// The index of the active layout.
+    pub active: usize,
+
+    pub(crate) platform_specific: platform_impl::KeyboardLayoutInfoExtra,
+}
+
+impl KeyboardLayoutInfo {
+    /// Returns the key the physical key produces under the active layout, when the `modifiers`
+    /// are held, without the key being pressed.
+    ///
+    /// This is useful to display shortcuts, like `Ctrl+Z`, with the keys of the user's layout.
+    /// Only the shift, control, alt and super modifiers are considered.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Web / iOS / Android / Orbital:** Unsupported, always returns
+    ///   [`Key::Unidentified`].
+    pub fn key_for_physical(&self, physical_key: PhysicalKey, modifiers: ModifiersState) -> Key {
+        self.platform_specific
+            .key_for_physical(self.active, physical_key, modifiers)
+    }
+}
+
+/// A keyboard layout.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct KeyboardLayout {
+    /// The human readable name of the layout, like `French (AZERTY)`.
+    pub name: String,
+
+    /// The short name of the layout, like `fr`, when it's known.
+    pub layout: Option<String>,
+
+    /// The variant of the layout, like `azerty`, when it's known.
+    pub variant: Option<String>,
+}
+
 bitflags! {
     /// Represents the current state of the keyboard modifiers
     ///
@@ -1717,6 +1784,21 @@ impl ModifiersState {
     }
 }
 
+bitflags! {
+    /// The state of the lock keys, see [`Modifiers::lock_state`].
+    ///
+    /// [`Modifiers::lock_state`]: crate::event::Modifiers::lock_state
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct LockState: u32 {
+        /// The "caps lock" is on.
+        const CAPS_LOCK = 0b001;
+        /// The "num lock" is on.
+        const NUM_LOCK = 0b010;
+        /// The "scroll lock" is on.
+        const SCROLL_LOCK = 0b100;
+    }
+}
+
 /// The state of the particular modifiers key.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ModifiersKeyState {
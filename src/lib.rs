@@ -98,7 +98,7 @@
 //!             window.request_redraw();
 //!         },
 //!         Event::WindowEvent {
-//!             event: WindowEvent::RedrawRequested,
+//!             event: WindowEvent::RedrawRequested { .. },
 //!             ..
 //!         } => {
 //!             // Redraw the application.
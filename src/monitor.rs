@@ -6,7 +6,8 @@
 //! - [`EventLoopWindowTarget::available_monitors`](crate::event_loop::EventLoopWindowTarget::available_monitors).
 //! - [`Window::available_monitors`](crate::window::Window::available_monitors).
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    error::ExternalError,
     platform_impl,
 };
 
@@ -130,6 +131,44 @@ impl MonitorHandle {
         self.inner.position()
     }
 
+    /// Returns the top-left corner position of the monitor in the logical coordinates of the
+    /// larger full screen area.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The position in the compositor's layout, from `xdg_output` when available.
+    /// - **Others:** The [`position`](Self::position) divided by the
+    ///   [`scale_factor`](Self::scale_factor).
+    #[inline]
+    pub fn logical_position(&self) -> LogicalPosition<i32> {
+        self.inner.logical_position()
+    }
+
+    /// Returns the size of the monitor in logical pixels, with its [`transform`] applied.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The size in the compositor's layout, from `xdg_output` when available.
+    /// - **Others:** The [`size`](Self::size) divided by the [`scale_factor`](Self::scale_factor).
+    ///
+    /// [`transform`]: Self::transform
+    #[inline]
+    pub fn logical_size(&self) -> LogicalSize<u32> {
+        self.inner.logical_size()
+    }
+
+    /// Returns the rotation and the flip applied to the contents of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The rotation of the RandR CRTC.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, always returns
+    ///   [`MonitorTransform::Normal`].
+    #[inline]
+    pub fn transform(&self) -> MonitorTransform {
+        self.inner.transform()
+    }
+
     /// The monitor refresh rate used by the system.
     ///
     /// Return `Some` if succeed, or `None` if failed, which usually happens when the monitor
@@ -159,6 +198,80 @@ impl MonitorHandle {
         self.inner.scale_factor()
     }
 
+    /// Returns the physical size of the monitor in millimeters, as `(width, height)`.
+    ///
+    /// Returns `None` if the size is unknown, like for projectors.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, always returns `None`.
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.inner.physical_size_mm()
+    }
+
+    /// Returns the manufacturer of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The three letter PNP ID from the EDID, like `DEL`.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, always returns `None`.
+    #[inline]
+    pub fn manufacturer(&self) -> Option<String> {
+        self.inner.manufacturer()
+    }
+
+    /// Returns the model of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The monitor name from the EDID, or the product code when it's missing.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, always returns `None`.
+    #[inline]
+    pub fn model(&self) -> Option<String> {
+        self.inner.model()
+    }
+
+    /// Returns the raw EDID of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, always
+    ///   returns `None`.
+    #[inline]
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        self.inner.edid()
+    }
+
+    /// Returns the gamma ramp of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The ramp can't be queried, so this returns the ramp last set with
+    ///   [`set_gamma_ramp`](Self::set_gamma_ramp), or a linear ramp. Requires
+    ///   `zwlr_gamma_control_manager_v1`.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn gamma_ramp(&self) -> Result<GammaRamp, ExternalError> {
+        self.inner.gamma_ramp()
+    }
+
+    /// Sets the gamma ramp of the monitor.
+    ///
+    /// This is meant for fullscreen windows: the original ramp is restored once the fullscreen
+    /// window on the monitor leaves fullscreen or is closed, and when the event loop is dropped.
+    /// The ramp is resampled to the size used by the monitor, if needed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires `zwlr_gamma_control_manager_v1`. Only one client at a time can
+    ///   control the gamma of an output.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), ExternalError> {
+        self.inner.set_gamma_ramp(ramp)
+    }
+
     /// Returns all fullscreen video modes supported by this monitor.
     ///
     /// ## Platform-specific
@@ -171,3 +284,104 @@ impl MonitorHandle {
             .map(|video_mode| VideoModeHandle { video_mode })
     }
 }
+
+/// The transform applied to the contents of the monitor.
+///
+/// The rotation is counter-clockwise, and the flipped variants are flipped around the vertical
+/// axis before being rotated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonitorTransform {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl MonitorTransform {
+    /// Whether the transform swaps the width and the height of the monitor.
+    #[inline]
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Flipped90 | Self::Flipped270
+        )
+    }
+}
+
+/// The gamma ramp of a monitor, mapping the input intensity of each channel to the output
+/// intensity.
+///
+/// All the channels should have the same length.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GammaRamp {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl GammaRamp {
+    /// Creates a ramp with `size` entries for the given gamma, where `1.0` is linear and
+    /// higher values are brighter.
+    pub fn from_gamma(size: usize, gamma: f64) -> Self {
+        let last = size.saturating_sub(1).max(1) as f64;
+        let channel: Vec<u16> = (0..size)
+            .map(|i| ((i as f64 / last).powf(1.0 / gamma) * u16::MAX as f64).round() as u16)
+            .collect();
+
+        Self {
+            red: channel.clone(),
+            green: channel.clone(),
+            blue: channel,
+        }
+    }
+
+    /// The number of entries in the ramp.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.red.len()
+    }
+
+    /// Whether the ramp has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.red.is_empty()
+    }
+
+    /// Resample the ramp to `size` entries with linear interpolation.
+    #[cfg_attr(not(any(x11_platform, wayland_platform)), allow(dead_code))]
+    pub(crate) fn resample(&self, size: usize) -> Self {
+        fn channel(values: &[u16], size: usize) -> Vec<u16> {
+            if values.len() == size {
+                return values.to_vec();
+            }
+
+            let Some(&last_value) = values.last() else {
+                return vec![0; size];
+            };
+
+            let scale =
+                values.len().saturating_sub(1) as f64 / size.saturating_sub(1).max(1) as f64;
+            (0..size)
+                .map(|i| {
+                    let position = i as f64 * scale;
+                    let index = position as usize;
+                    let fraction = position - index as f64;
+                    let low = values[index] as f64;
+                    let high = values.get(index + 1).copied().unwrap_or(last_value) as f64;
+                    (low + (high - low) * fraction).round() as u16
+                })
+                .collect()
+        }
+
+        Self {
+            red: channel(&self.red, size),
+            green: channel(&self.green, size),
+            blue: channel(&self.blue, size),
+        }
+    }
+}
@@ -20,6 +20,8 @@ pub mod web;
 pub mod windows;
 #[cfg(any(x11_platform, docsrs))]
 pub mod x11;
+#[cfg(any(x11_platform, wayland_platform, docsrs))]
+pub mod xkb;
 
 #[cfg(any(
     windows_platform,
@@ -1,5 +1,7 @@
 use crate::{
-    event_loop::{EventLoopBuilder, EventLoopWindowTarget},
+    error::NotSupportedError,
+    event_loop::{AsyncRequestSerial, EventLoopBuilder, EventLoopWindowTarget},
+    keyboard::RepeatInfo,
     monitor::MonitorHandle,
     window::{Window, WindowBuilder},
 };
@@ -10,6 +12,11 @@ pub use crate::window::Theme;
 pub trait EventLoopWindowTargetExtWayland {
     /// True if the [`EventLoopWindowTarget`] uses Wayland.
     fn is_wayland(&self) -> bool;
+
+    /// The key repeat the compositor requested for the keyboard.
+    ///
+    /// Returns `None` when there's no keyboard yet, or on X11.
+    fn repeat_info(&self) -> Option<RepeatInfo>;
 }
 
 impl EventLoopWindowTargetExtWayland for EventLoopWindowTarget {
@@ -17,6 +24,11 @@ impl EventLoopWindowTargetExtWayland for EventLoopWindowTarget {
     fn is_wayland(&self) -> bool {
         self.p.is_wayland()
     }
+
+    #[inline]
+    fn repeat_info(&self) -> Option<RepeatInfo> {
+        self.p.repeat_info()
+    }
 }
 
 /// Additional methods on [`EventLoopBuilder`] that are specific to Wayland.
@@ -29,6 +41,18 @@ pub trait EventLoopBuilderExtWayland {
     /// By default, the window is only allowed to be created on the main
     /// thread, to make platform compatibility easier.
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
+
+    /// Whether winit repeats the held keys, following the [`RepeatInfo`] of the compositor.
+    ///
+    /// The compositor only reports the key presses and releases, so the repeats are synthesized
+    /// by winit and marked with [`KeyEvent::is_generated`]. Disable them to get only the raw
+    /// presses and releases, e.g. to implement a custom repeat. This could be changed for each
+    /// window with [`WindowExtWayland::set_synthetic_key_repeat`].
+    ///
+    /// The repeat is enabled by default.
+    ///
+    /// [`KeyEvent::is_generated`]: crate::event::KeyEvent::is_generated
+    fn with_synthetic_key_repeat(&mut self, synthetic_key_repeat: bool) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
@@ -43,12 +67,59 @@ impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
         self.platform_specific.any_thread = any_thread;
         self
     }
+
+    #[inline]
+    fn with_synthetic_key_repeat(&mut self, synthetic_key_repeat: bool) -> &mut Self {
+        self.platform_specific.no_synthetic_key_repeat = !synthetic_key_repeat;
+        self
+    }
 }
 
 /// Additional methods on [`Window`] that are specific to Wayland.
-pub trait WindowExtWayland {}
+pub trait WindowExtWayland {
+    /// Sets the window icon by its name in the icon theme, e.g. `"firefox"`.
+    ///
+    /// When the window also has an icon set with [`Window::set_window_icon`], the compositor
+    /// falls back to it if the named icon can't be found. Passing `None` removes the name.
+    ///
+    /// This requires the `xdg_toplevel_icon_manager_v1` protocol, and does nothing on X11.
+    fn set_icon_name(&self, icon_name: Option<String>);
 
-impl WindowExtWayland for Window {}
+    /// Exports the window, so other processes could make their windows transient for it.
+    ///
+    /// The handle is delivered with the [`WindowEvent::ExportHandleDone`] event, and could be
+    /// passed to [`WindowBuilderExtWayland::with_parent_handle`] in the other process. It stays
+    /// valid while the window is alive, until the window is exported again.
+    ///
+    /// This requires the `zxdg_exporter_v2` protocol, and returns [`NotSupportedError`]
+    /// otherwise or on X11.
+    ///
+    /// [`WindowEvent::ExportHandleDone`]: crate::event::WindowEvent::ExportHandleDone
+    fn export_handle(&self) -> Result<AsyncRequestSerial, NotSupportedError>;
+
+    /// Whether winit repeats the held keys while the window is focused.
+    ///
+    /// See [`EventLoopBuilderExtWayland::with_synthetic_key_repeat`] for details, which sets
+    /// the initial value. This does nothing on X11.
+    fn set_synthetic_key_repeat(&self, synthetic_key_repeat: bool);
+}
+
+impl WindowExtWayland for Window {
+    #[inline]
+    fn set_icon_name(&self, icon_name: Option<String>) {
+        self.window.set_icon_name(icon_name)
+    }
+
+    #[inline]
+    fn export_handle(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
+        self.window.export_handle()
+    }
+
+    #[inline]
+    fn set_synthetic_key_repeat(&self, synthetic_key_repeat: bool) {
+        self.window.set_synthetic_key_repeat(synthetic_key_repeat)
+    }
+}
 
 /// Additional methods on [`WindowBuilder`] that are specific to Wayland.
 pub trait WindowBuilderExtWayland {
@@ -60,6 +131,21 @@ pub trait WindowBuilderExtWayland {
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     fn with_name(self, general: impl Into<String>, instance: impl Into<String>) -> Self;
+
+    /// Build window with the icon of the given name from the icon theme.
+    ///
+    /// See [`WindowExtWayland::set_icon_name`] for details.
+    fn with_icon_name(self, icon_name: impl Into<String>) -> Self;
+
+    /// Build window transient for the foreign window with the given handle.
+    ///
+    /// The handle is obtained with [`WindowExtWayland::export_handle`] in the process owning the
+    /// parent window. This requires the `zxdg_importer_v2` protocol.
+    ///
+    /// On X11, see [`WindowBuilderExtX11::with_transient_for`].
+    ///
+    /// [`WindowBuilderExtX11::with_transient_for`]: crate::platform::x11::WindowBuilderExtX11::with_transient_for
+    fn with_parent_handle(self, handle: String) -> Self;
 }
 
 impl WindowBuilderExtWayland for WindowBuilder {
@@ -71,6 +157,18 @@ impl WindowBuilderExtWayland for WindowBuilder {
         ));
         self
     }
+
+    #[inline]
+    fn with_icon_name(mut self, icon_name: impl Into<String>) -> Self {
+        self.window.platform_specific.wayland.icon_name = Some(icon_name.into());
+        self
+    }
+
+    #[inline]
+    fn with_parent_handle(mut self, handle: String) -> Self {
+        self.window.platform_specific.wayland.parent_handle = Some(handle);
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Wayland.
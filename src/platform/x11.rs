@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    error::ExternalError,
+    event::HotkeyId,
     event_loop::{EventLoopBuilder, EventLoopWindowTarget},
+    keyboard::{ModifiersState, PhysicalKey},
     monitor::MonitorHandle,
     window::{Window, WindowBuilder},
 };
@@ -89,10 +94,63 @@ pub fn register_xlib_error_hook(hook: XlibErrorHook) {
     }
 }
 
+/// The desktop settings published by the
+/// [XSETTINGS](https://specifications.freedesktop.org/xsettings-spec/xsettings-latest.html)
+/// manager, like `gsd-xsettings` or `xsettingsd`.
+///
+/// The settings are `None` when the manager doesn't publish them.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct XSettings {
+    /// The `Xft/DPI`, in dots per inch.
+    pub dpi: Option<f64>,
+
+    /// The `Gdk/WindowScalingFactor`, the integer scale used by GTK.
+    pub window_scaling_factor: Option<u32>,
+
+    /// The `Net/DoubleClickTime`, the maximum time between the clicks of a double click.
+    pub double_click_time: Option<Duration>,
+
+    /// The `Gtk/CursorThemeName`, the Xcursor theme used to load the cursors.
+    pub cursor_theme_name: Option<String>,
+}
+
 /// Additional methods on [`EventLoopWindowTarget`] that are specific to X11.
 pub trait EventLoopWindowTargetExtX11 {
     /// True if the [`EventLoopWindowTarget`] uses X11.
     fn is_x11(&self) -> bool;
+
+    /// The current desktop settings published over XSETTINGS.
+    ///
+    /// The changes of the DPI and the window scaling factor are reported with
+    /// [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// Returns the default, empty settings when the [`EventLoopWindowTarget`] doesn't use X11.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn xsettings(&self) -> XSettings;
+
+    /// Register a hotkey which is reported even when none of the windows has the focus.
+    ///
+    /// The presses of the `key` with exactly the `modifiers` are reported as
+    /// [`DeviceEvent::GlobalHotkey`] with the returned id, whatever the state of the caps lock
+    /// and the num lock. The hotkey is grabbed on the root window, so the other clients don't
+    /// receive it.
+    ///
+    /// Returns an error when the hotkey is already grabbed, by this or by another client, or
+    /// when the [`EventLoopWindowTarget`] doesn't use X11.
+    ///
+    /// [`DeviceEvent::GlobalHotkey`]: crate::event::DeviceEvent::GlobalHotkey
+    fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: PhysicalKey,
+    ) -> Result<HotkeyId, ExternalError>;
+
+    /// Unregister a hotkey registered with [`register_global_hotkey`].
+    ///
+    /// [`register_global_hotkey`]: Self::register_global_hotkey
+    fn unregister_global_hotkey(&self, id: HotkeyId);
 }
 
 impl EventLoopWindowTargetExtX11 for EventLoopWindowTarget {
@@ -100,6 +158,25 @@ impl EventLoopWindowTargetExtX11 for EventLoopWindowTarget {
     fn is_x11(&self) -> bool {
         !self.p.is_wayland()
     }
+
+    #[inline]
+    fn xsettings(&self) -> XSettings {
+        self.p.xsettings()
+    }
+
+    #[inline]
+    fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: PhysicalKey,
+    ) -> Result<HotkeyId, ExternalError> {
+        self.p.register_global_hotkey(modifiers, key)
+    }
+
+    #[inline]
+    fn unregister_global_hotkey(&self, id: HotkeyId) {
+        self.p.unregister_global_hotkey(id)
+    }
 }
 
 /// Additional methods on [`EventLoopBuilder`] that are specific to X11.
@@ -185,6 +262,12 @@ pub trait WindowBuilderExtX11 {
     /// # Ok(()) }
     /// ```
     fn with_embed_parent_window(self, parent_window_id: XWindow) -> Self;
+
+    /// Build window transient for the given window, which could belong to another process.
+    ///
+    /// This sets `WM_TRANSIENT_FOR`, so the window manager keeps the window above its parent,
+    /// which is useful for dialogs.
+    fn with_transient_for(self, parent_window_id: XWindow) -> Self;
 }
 
 impl WindowBuilderExtX11 for WindowBuilder {
@@ -232,6 +315,12 @@ impl WindowBuilderExtX11 for WindowBuilder {
         self.window.platform_specific.x11.embed_window = Some(parent_window_id);
         self
     }
+
+    #[inline]
+    fn with_transient_for(mut self, parent_window_id: XWindow) -> Self {
+        self.window.platform_specific.x11.transient_for = Some(parent_window_id);
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to X11.
@@ -0,0 +1,80 @@
+//! Custom XKB keymaps on X11 and Wayland.
+//!
+//! By default, the keys are translated with the keymap of the X server or of the compositor.
+//! [`EventLoopBuilderExtUnix::with_xkb_keymap`] replaces it with a fixed keymap, which is useful
+//! for kiosks that need a given layout, or to test the key translation against a known layout.
+//!
+//! The custom keymap is only used to produce the [`Key`]s and the text of the key events. The
+//! modifiers and the active layout are still the ones of the server, and the text committed by
+//! the input methods isn't affected.
+//!
+//! [`Key`]: crate::keyboard::Key
+
+use crate::event_loop::EventLoopBuilder;
+
+/// The source of a custom XKB keymap.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeymapSource {
+    /// Compile the keymap from the RMLVO names, like `setxkbmap` does.
+    Names(RuleNames),
+
+    /// A keymap in the XKB text format, like the output of `xkbcomp -xkb $DISPLAY -`.
+    String(String),
+}
+
+/// The RMLVO names of a keymap.
+///
+/// The names which are `None` use the defaults of libxkbcommon, which can be changed with the
+/// `XKB_DEFAULT_RULES`, `XKB_DEFAULT_MODEL`, `XKB_DEFAULT_LAYOUT`, `XKB_DEFAULT_VARIANT` and
+/// `XKB_DEFAULT_OPTIONS` environment variables.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct RuleNames {
+    /// The rules file, like `evdev`.
+    pub rules: Option<String>,
+
+    /// The keyboard model, like `pc105`.
+    pub model: Option<String>,
+
+    /// The comma separated layouts, like `us,fr`.
+    pub layout: Option<String>,
+
+    /// The comma separated variants of the layouts, like `dvorak,azerty`.
+    pub variant: Option<String>,
+
+    /// The comma separated options, like `ctrl:nocaps`.
+    pub options: Option<String>,
+}
+
+/// Additional methods on [`EventLoopBuilder`] that are specific to X11 and Wayland.
+pub trait EventLoopBuilderExtUnix {
+    /// Translate the keys with the given keymap instead of the keymap of the server.
+    ///
+    /// When the keymap fails to compile, a warning is logged and the keymap of the server is
+    /// used.
+    fn with_xkb_keymap(&mut self, keymap: KeymapSource) -> &mut Self;
+
+    /// Whether winit combines the dead keys and the `Compose` key sequences, `true` by default.
+    ///
+    /// When disabled, the dead keys are reported as [`Key::Dead`] without being combined with
+    /// the next key, and no [`WindowEvent::ComposeState`] events are sent, so the application
+    /// can handle them itself.
+    ///
+    /// [`Key::Dead`]: crate::keyboard::Key::Dead
+    /// [`WindowEvent::ComposeState`]: crate::event::WindowEvent::ComposeState
+    fn with_xkb_compose(&mut self, compose: bool) -> &mut Self;
+}
+
+impl<T> EventLoopBuilderExtUnix for EventLoopBuilder<T> {
+    #[inline]
+    fn with_xkb_keymap(&mut self, keymap: KeymapSource) -> &mut Self {
+        self.platform_specific.xkb_keymap = Some(keymap);
+        self
+    }
+
+    #[inline]
+    fn with_xkb_compose(&mut self, compose: bool) -> &mut Self {
+        self.platform_specific.no_xkb_compose = !compose;
+        self
+    }
+}
@@ -27,7 +27,8 @@ use crate::{
     event_loop::{self, ControlFlow, DeviceEvents, EventLoopWindowTarget as RootELW},
     platform::pump_events::PumpStatus,
     window::{
-        self, CursorGrabMode, ImePurpose, ResizeDirection, Theme, WindowButtons, WindowLevel,
+        self, ContentType, CursorGrabMode, ImeHints, ImePurpose, PresentationHint, ResizeDirection,
+        Theme, WindowButtons, WindowLevel,
     },
 };
 use crate::{error::EventLoopError, platform_impl::Fullscreen};
@@ -139,6 +140,20 @@ impl RedrawRequester {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {}
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfoExtra {}
+
+impl KeyboardLayoutInfoExtra {
+    pub fn key_for_physical(
+        &self,
+        _layout: usize,
+        _physical_key: crate::keyboard::PhysicalKey,
+        _modifiers: crate::keyboard::ModifiersState,
+    ) -> crate::keyboard::Key {
+        crate::keyboard::Key::Unidentified(crate::keyboard::NativeKey::Unidentified)
+    }
+}
+
 pub struct EventLoop<T: 'static> {
     android_app: AndroidApp,
     window_target: event_loop::EventLoopWindowTarget,
@@ -358,7 +373,7 @@ impl<T: 'static> EventLoop<T> {
                 pending_redraw = false;
                 let event = event::Event::WindowEvent {
                     window_id: window::WindowId(WindowId),
-                    event: event::WindowEvent::RedrawRequested,
+                    event: event::WindowEvent::RedrawRequested { frame_time: None },
                 };
                 callback(event, self.window_target());
             }
@@ -462,6 +477,7 @@ impl<T: 'static> EventLoop<T> {
                                     logical_key: keycodes::to_logical(key_char, keycode),
                                     location: keycodes::to_location(keycode),
                                     repeat: key.repeat_count() > 0,
+                                    is_generated: false,
                                     text: None,
                                     platform_specific: KeyEventExtra {},
                                 },
@@ -683,6 +699,23 @@ impl EventLoopWindowTarget {
         v
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
@@ -831,6 +864,10 @@ impl Window {
         self.redraw_requester.request_redraw()
     }
 
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     pub fn pre_present_notify(&self) {}
 
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
@@ -927,6 +964,10 @@ impl Window {
 
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     pub fn focus_window(&self) {}
 
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
@@ -971,6 +1012,27 @@ impl Window {
         ))
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     #[cfg(feature = "rwh_04")]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
         use rwh_04::HasRawWindowHandle;
@@ -1035,8 +1097,16 @@ impl Window {
         None
     }
 
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    pub fn set_content_type(&self, _content_type: ContentType) {}
+
     pub fn has_focus(&self) -> bool {
         *HAS_FOCUS.read().unwrap()
     }
@@ -1111,6 +1181,49 @@ impl MonitorHandle {
         None
     }
 
+    pub fn logical_position(&self) -> crate::dpi::LogicalPosition<i32> {
+        self.position().to_logical(self.scale_factor())
+    }
+
+    pub fn logical_size(&self) -> crate::dpi::LogicalSize<u32> {
+        self.size().to_logical(self.scale_factor())
+    }
+
+    pub fn transform(&self) -> crate::monitor::MonitorTransform {
+        crate::monitor::MonitorTransform::Normal
+    }
+
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    pub fn model(&self) -> Option<String> {
+        None
+    }
+
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    pub fn gamma_ramp(&self) -> Result<crate::monitor::GammaRamp, crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn set_gamma_ramp(
+        &self,
+        _ramp: &crate::monitor::GammaRamp,
+    ) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let size = self.size().into();
         // FIXME this is not the real refresh rate
@@ -75,7 +75,7 @@ impl Event<Never> {
         matches!(
             self,
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { .. },
                 ..
             }
         )
@@ -763,7 +763,7 @@ pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
         .map(|window| {
             EventWrapper::StaticEvent(Event::WindowEvent {
                 window_id: RootWindowId(window.id()),
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { frame_time: None },
             })
         })
         .collect();
@@ -47,6 +47,23 @@ impl EventLoopWindowTarget {
         Some(MonitorHandle::new(UIScreen::main(self.mtm)))
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
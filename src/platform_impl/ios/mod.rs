@@ -101,6 +101,20 @@ pub(crate) const DEVICE_ID: RootDeviceId = RootDeviceId(DeviceId);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyEventExtra {}
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfoExtra {}
+
+impl KeyboardLayoutInfoExtra {
+    pub fn key_for_physical(
+        &self,
+        _layout: usize,
+        _physical_key: crate::keyboard::PhysicalKey,
+        _modifiers: crate::keyboard::ModifiersState,
+    ) -> crate::keyboard::Key {
+        crate::keyboard::Key::Unidentified(crate::keyboard::NativeKey::Unidentified)
+    }
+}
+
 #[derive(Debug)]
 pub enum OsError {}
 
@@ -196,6 +196,49 @@ impl MonitorHandle {
         )
     }
 
+    pub fn logical_position(&self) -> crate::dpi::LogicalPosition<i32> {
+        self.position().to_logical(self.scale_factor())
+    }
+
+    pub fn logical_size(&self) -> crate::dpi::LogicalSize<u32> {
+        self.size().to_logical(self.scale_factor())
+    }
+
+    pub fn transform(&self) -> crate::monitor::MonitorTransform {
+        crate::monitor::MonitorTransform::Normal
+    }
+
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    pub fn model(&self) -> Option<String> {
+        None
+    }
+
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    pub fn gamma_ramp(&self) -> Result<crate::monitor::GammaRamp, crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn set_gamma_ramp(
+        &self,
+        _ramp: &crate::monitor::GammaRamp,
+    ) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         MainThreadMarker::run_on_main(|mtm| {
             let ui_screen = self.ui_screen(mtm);
@@ -56,7 +56,7 @@ declare_class!(
                 mtm,
                 EventWrapper::StaticEvent(Event::WindowEvent {
                     window_id: RootWindowId(window.id()),
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { frame_time: None },
                 }),
             );
             let _: () = unsafe { msg_send![super(self), drawRect: rect] };
@@ -22,8 +22,9 @@ use crate::{
         app_state, monitor, EventLoopWindowTarget, Fullscreen, MonitorHandle,
     },
     window::{
-        CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-        WindowButtons, WindowId as RootWindowId, WindowLevel,
+        ContentType, CursorGrabMode, ImeHints, ImePurpose, PresentationHint, ResizeDirection,
+        Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowId as RootWindowId,
+        WindowLevel,
     },
 };
 
@@ -73,6 +74,10 @@ impl Inner {
         }
     }
 
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     pub fn pre_present_notify(&self) {}
 
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
@@ -206,6 +211,23 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_minimized(&self, _minimized: bool) {
         warn!("`Window::set_minimized` is ignored on iOS")
     }
@@ -302,6 +324,14 @@ impl Inner {
         warn!("`Window::set_ime_allowed` is ignored on iOS")
     }
 
+    pub fn set_ime_hints(&self, _hints: ImeHints) {
+        warn!("`Window::set_ime_hints` is ignored on iOS")
+    }
+
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {
+        warn!("`Window::set_ime_surrounding_text` is ignored on iOS")
+    }
+
     pub fn focus_window(&self) {
         warn!("`Window::set_focus` is ignored on iOS")
     }
@@ -372,8 +402,16 @@ impl Inner {
         None
     }
 
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    pub fn set_content_type(&self, _content_type: ContentType) {}
+
     pub fn has_focus(&self) -> bool {
         self.window.isKeyWindow()
     }
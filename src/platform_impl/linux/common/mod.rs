@@ -1,2 +1,3 @@
 pub mod keymap;
+pub mod portal;
 pub mod xkb_state;
@@ -0,0 +1,218 @@
+//! Reading of the appearance settings from the XDG desktop portal.
+//!
+//! The settings are read with `zbus` on a thread of their own, which then waits for the
+//! `SettingChanged` signals of the portal.
+
+use crate::window::{AccentColor, Theme};
+
+/// The appearance settings of the desktop.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Appearance {
+    /// The theme from the `color-scheme`, `None` when there's no preference.
+    pub theme: Option<Theme>,
+
+    /// The `accent-color`.
+    pub accent_color: Option<AccentColor>,
+}
+
+/// Watch the appearance settings, calling the callback once they're read and whenever they
+/// change, until it returns `false`.
+///
+/// Does nothing without the `zbus` feature.
+#[cfg(not(feature = "zbus"))]
+pub fn watch_appearance<F>(_callback: F)
+where
+    F: FnMut(Appearance) -> bool + Send + 'static,
+{
+}
+
+#[cfg(feature = "zbus")]
+pub use self::dbus::watch_appearance;
+
+#[cfg(feature = "zbus")]
+mod dbus {
+    use std::thread;
+
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use super::Appearance;
+    use crate::window::{AccentColor, Theme};
+
+    /// The namespace of the appearance settings.
+    const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
+    const UNKNOWN_METHOD: &str = "org.freedesktop.DBus.Error.UnknownMethod";
+
+    impl Appearance {
+        /// Update the setting with the given key, returning whether it's an appearance setting.
+        fn update(&mut self, key: &str, value: &Value<'_>) -> bool {
+            match key {
+                "color-scheme" => self.theme = parse_theme(value),
+                "accent-color" => self.accent_color = parse_accent_color(value),
+                _ => return false,
+            }
+
+            true
+        }
+    }
+
+    /// The theme of the `u` of the `color-scheme`, which is `1` for dark and `2` for light.
+    fn parse_theme(value: &Value<'_>) -> Option<Theme> {
+        match value {
+            Value::U32(1) => Some(Theme::Dark),
+            Value::U32(2) => Some(Theme::Light),
+            _ => None,
+        }
+    }
+
+    /// The `(ddd)` of the `accent-color`, out of range channels mean it's unset.
+    fn parse_accent_color(value: &Value<'_>) -> Option<AccentColor> {
+        let channels = match value {
+            Value::Structure(channels) => channels.fields(),
+            _ => return None,
+        };
+
+        let channel = |channel: &Value<'_>| match *channel {
+            Value::F64(channel) if (0.0..=1.0).contains(&channel) => {
+                Some((channel * 255.).round() as u8)
+            }
+            _ => None,
+        };
+
+        match channels {
+            [red, green, blue] => Some(AccentColor {
+                red: channel(red)?,
+                green: channel(green)?,
+                blue: channel(blue)?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Watch the appearance settings on a thread, see [`super::watch_appearance`].
+    pub fn watch_appearance<F>(mut callback: F)
+    where
+        F: FnMut(Appearance) -> bool + Send + 'static,
+    {
+        let result = thread::Builder::new()
+            .name("winit portal".to_owned())
+            .spawn(move || {
+                if let Err(err) = run(&mut callback) {
+                    log::warn!("Failed to watch the appearance settings of the portal: {err}");
+                }
+            });
+
+        if let Err(err) = result {
+            log::warn!("Failed to spawn the thread reading the portal: {err}");
+        }
+    }
+
+    fn run(callback: &mut dyn FnMut(Appearance) -> bool) -> zbus::Result<()> {
+        let connection = Connection::session()?;
+        let proxy = Proxy::new(
+            &connection,
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+        )?;
+
+        // Subscribe before reading, so no change is missed in between.
+        let changes =
+            proxy.receive_signal_with_args("SettingChanged", &[(0, APPEARANCE_NAMESPACE)])?;
+
+        let mut appearance = Appearance::default();
+        for key in ["color-scheme", "accent-color"] {
+            if let Some(value) = read_setting(&proxy, key)? {
+                appearance.update(key, &value);
+            }
+        }
+
+        if !callback(appearance) {
+            return Ok(());
+        }
+
+        for message in changes {
+            let (namespace, key, value): (String, String, OwnedValue) = match message.body() {
+                Ok(args) => args,
+                Err(err) => {
+                    log::warn!("Invalid setting change of the portal: {err}");
+                    continue;
+                }
+            };
+
+            let prev_appearance = appearance;
+            if namespace == APPEARANCE_NAMESPACE
+                && appearance.update(&key, &value)
+                && appearance != prev_appearance
+                && !callback(appearance)
+            {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the appearance setting, `None` when the portal doesn't have it.
+    fn read_setting(proxy: &Proxy<'_>, key: &str) -> zbus::Result<Option<OwnedValue>> {
+        let result = proxy.call::<_, _, OwnedValue>("ReadOne", &(APPEARANCE_NAMESPACE, key));
+        let err = match result {
+            Ok(value) => return Ok(Some(value)),
+            Err(err) => err,
+        };
+
+        match err {
+            // The deprecated `Read` of the older portals wraps the value in another variant.
+            zbus::Error::MethodError(name, ..) if name.as_str() == UNKNOWN_METHOD => {
+                match proxy.call::<_, _, OwnedValue>("Read", &(APPEARANCE_NAMESPACE, key)) {
+                    Ok(value) => match Value::from(value) {
+                        Value::Value(value) => Ok(Some((*value).into())),
+                        _ => Ok(None),
+                    },
+                    Err(zbus::Error::MethodError(..)) => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }
+            // The setting doesn't exist.
+            zbus::Error::MethodError(..) => Ok(None),
+            err => Err(err),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use zbus::zvariant::StructureBuilder;
+
+        use super::*;
+
+        #[test]
+        fn typed_settings() {
+            let mut appearance = Appearance::default();
+            assert!(appearance.update("color-scheme", &Value::U32(1)));
+            assert_eq!(appearance.theme, Some(Theme::Dark));
+
+            let accent_color = StructureBuilder::new()
+                .add_field(1.)
+                .add_field(0.5)
+                .add_field(0.)
+                .build();
+            assert!(appearance.update("accent-color", &Value::Structure(accent_color)));
+            assert_eq!(
+                appearance.accent_color,
+                Some(AccentColor {
+                    red: 255,
+                    green: 128,
+                    blue: 0,
+                })
+            );
+
+            // The values must not be wrapped in another variant.
+            let wrapped = Value::Value(Box::new(Value::U32(2)));
+            assert!(appearance.update("color-scheme", &wrapped));
+            assert_eq!(appearance.theme, None);
+
+            assert!(!appearance.update("contrast", &Value::U32(1)));
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use std::convert::TryInto;
 use std::env;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::os::unix::ffi::OsStringExt;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use once_cell::sync::Lazy;
 use smol_str::SmolStr;
@@ -17,12 +18,15 @@ use {memmap2::MmapOptions, std::os::unix::io::OwnedFd};
 #[cfg(feature = "x11")]
 use {x11_dl::xlib_xcb::xcb_connection_t, xkbcommon_dl::x11::xkbcommon_x11_handle};
 
-use crate::event::KeyEvent;
+use crate::event::{ComposeStatus, KeyEvent};
+use crate::platform::xkb::KeymapSource;
 use crate::platform_impl::common::keymap;
-use crate::platform_impl::KeyEventExtra;
+use crate::platform_impl::{KeyEventExtra, KeyboardLayoutInfoExtra};
 use crate::{
     event::ElementState,
-    keyboard::{Key, KeyLocation, PhysicalKey},
+    keyboard::{
+        Key, KeyLocation, KeyboardLayout, KeyboardLayoutInfo, LockState, NativeKey, PhysicalKey,
+    },
 };
 
 // TODO: Wire this up without using a static `AtomicBool`.
@@ -52,6 +56,12 @@ pub struct KbdState {
     #[cfg(feature = "x11")]
     pub core_keyboard_id: i32,
     scratch_buffer: Vec<u8>,
+    /// The keymap set by the user, used instead of the keymaps of the server.
+    custom_keymap: Option<KeymapSource>,
+    /// The text of the keys of the compose sequence in progress.
+    compose_pending: Option<String>,
+    /// The change of the compose sequence caused by the last key event.
+    compose_update: Option<(Option<String>, ComposeStatus)>,
 }
 
 impl KbdState {
@@ -209,6 +219,9 @@ impl KbdState {
             #[cfg(feature = "x11")]
             core_keyboard_id: 0,
             scratch_buffer: Vec::new(),
+            custom_keymap: None,
+            compose_pending: None,
+            compose_update: None,
         };
 
         unsafe { me.init_compose() };
@@ -310,13 +323,17 @@ impl KbdState {
 
     #[cfg(feature = "x11")]
     pub unsafe fn init_with_x11_keymap(&mut self) {
-        if !self.xkb_keymap.is_null() {
+        if !self.xkb_keymap.is_null() && self.custom_keymap.is_none() {
             unsafe { self.de_init() };
         }
 
         // TODO: Support keyboards other than the "virtual core keyboard device".
         self.core_keyboard_id =
             unsafe { (XKBXH.xkb_x11_get_core_keyboard_device_id)(self.xcb_connection) };
+        if self.custom_keymap.is_some() {
+            return;
+        }
+
         let keymap = unsafe {
             (XKBXH.xkb_x11_keymap_new_from_device)(
                 self.xkb_context,
@@ -341,6 +358,10 @@ impl KbdState {
 
     #[cfg(feature = "wayland")]
     pub unsafe fn init_with_fd(&mut self, fd: OwnedFd, size: usize) {
+        if self.custom_keymap.is_some() {
+            return;
+        }
+
         if !self.xkb_keymap.is_null() {
             unsafe { self.de_init() };
         }
@@ -369,6 +390,55 @@ impl KbdState {
         unsafe { self.post_init(state, keymap) };
     }
 
+    /// Use the keymap set by the user instead of the keymaps of the server.
+    ///
+    /// The keymap of the server is kept when the custom keymap fails to compile.
+    pub fn set_custom_keymap(&mut self, source: KeymapSource) {
+        let keymap = unsafe { compile_keymap(self.xkb_context, &source) };
+        if keymap.is_null() {
+            log::warn!("Failed to compile the custom keymap, using the keymap of the server");
+            return;
+        }
+
+        if !self.xkb_keymap.is_null() {
+            unsafe { self.de_init() };
+        }
+
+        let state = unsafe { (XKBH.xkb_state_new)(keymap) };
+        unsafe { self.post_init(state, keymap) };
+        self.custom_keymap = Some(source);
+    }
+
+    /// Stop handling the dead keys and the compose sequences.
+    pub fn disable_compose(&mut self) {
+        unsafe {
+            if !self.xkb_compose_state.is_null() {
+                (XKBCH.xkb_compose_state_unref)(self.xkb_compose_state);
+                self.xkb_compose_state = ptr::null_mut();
+            }
+            if !self.xkb_compose_state_2.is_null() {
+                (XKBCH.xkb_compose_state_unref)(self.xkb_compose_state_2);
+                self.xkb_compose_state_2 = ptr::null_mut();
+            }
+            if !self.xkb_compose_table.is_null() {
+                (XKBCH.xkb_compose_table_unref)(self.xkb_compose_table);
+                self.xkb_compose_table = ptr::null_mut();
+            }
+        }
+        self.compose_pending = None;
+    }
+
+    /// Take the change of the compose sequence caused by the last key event, as the pending
+    /// text and the status.
+    pub fn take_compose_update(&mut self) -> Option<(Option<String>, ComposeStatus)> {
+        self.compose_update.take()
+    }
+
+    /// Whether the keymap was set by the user.
+    pub fn has_custom_keymap(&self) -> bool {
+        self.custom_keymap.is_some()
+    }
+
     pub fn key_repeats(&mut self, keycode: ffi::xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.xkb_keymap, keycode) == 1 }
     }
@@ -383,6 +453,82 @@ impl KbdState {
         self.mods_state
     }
 
+    /// The index of the effective layout.
+    pub fn active_layout(&self) -> usize {
+        if !self.ready() {
+            return 0;
+        }
+
+        unsafe {
+            (XKBH.xkb_state_serialize_layout)(
+                self.xkb_state,
+                xkb_state_component::XKB_STATE_LAYOUT_EFFECTIVE,
+            ) as usize
+        }
+    }
+
+    /// The current keymap in the XKB text format.
+    pub fn keymap_text(&self) -> Option<String> {
+        if !self.ready() {
+            return None;
+        }
+
+        let keymap = unsafe {
+            (XKBH.xkb_keymap_get_as_string)(
+                self.xkb_keymap,
+                ffi::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+            )
+        };
+        if keymap.is_null() {
+            return None;
+        }
+
+        let text = unsafe { CStr::from_ptr(keymap) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { libc::free(keymap as *mut _) };
+        Some(text)
+    }
+
+    /// The layouts of the keymap, without their RMLVO names, and the active one.
+    pub fn keyboard_layout_info(&self) -> KeyboardLayoutInfo {
+        if !self.ready() {
+            return KeyboardLayoutInfo::default();
+        }
+
+        let num_layouts = unsafe { (XKBH.xkb_keymap_num_layouts)(self.xkb_keymap) } as usize;
+        let text = self.keymap_text();
+        let names = text.as_deref().map(parse_layout_names).unwrap_or_default();
+
+        let (layout_names, variant_names) = match &self.custom_keymap {
+            Some(KeymapSource::Names(names)) => (names.layout.as_deref(), names.variant.as_deref()),
+            _ => (None, None),
+        };
+        let mut layout_names = layout_names.unwrap_or_default().split(',');
+        let mut variant_names = variant_names.unwrap_or_default().split(',');
+        let layouts = (0..num_layouts)
+            .map(|index| KeyboardLayout {
+                name: names.get(index).cloned().unwrap_or_default(),
+                layout: layout_names
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned),
+                variant: variant_names
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned),
+            })
+            .collect();
+
+        KeyboardLayoutInfo {
+            layouts,
+            active: self.active_layout(),
+            platform_specific: KeyboardLayoutInfoExtra {
+                keymap: text.and_then(XkbKeymap::from_string).map(Arc::new),
+            },
+        }
+    }
+
     pub fn process_key_event(
         &mut self,
         keycode: u32,
@@ -396,6 +542,8 @@ impl KbdState {
         let text = event.text();
         let (key_without_modifiers, _) = event.key_without_modifiers();
         let text_with_all_modifiers = event.text_with_all_modifiers();
+        let compose = event.compose;
+        self.update_compose(compose, &logical_key);
 
         let platform_specific = KeyEventExtra {
             text_with_all_modifiers,
@@ -409,10 +557,48 @@ impl KbdState {
             location,
             state,
             repeat,
+            is_generated: false,
             platform_specific,
         }
     }
 
+    /// Track the progress of the compose sequence after a key event.
+    fn update_compose(&mut self, compose: Option<XkbCompose>, key: &Key) {
+        let status = match compose {
+            Some(XkbCompose::Accepted(status)) => status,
+            // Releases, repeats and modifiers don't affect the sequence.
+            _ => {
+                self.compose_update = None;
+                return;
+            }
+        };
+
+        self.compose_update = match status {
+            ffi::xkb_compose_status::XKB_COMPOSE_COMPOSING => {
+                let pending = self.compose_pending.get_or_insert_with(String::new);
+                match key {
+                    Key::Dead(Some(c)) => pending.push(*c),
+                    Key::Character(text) => pending.push_str(text),
+                    _ => (),
+                }
+                Some((Some(pending.clone()), ComposeStatus::Composing))
+            }
+            ffi::xkb_compose_status::XKB_COMPOSE_COMPOSED => {
+                self.compose_pending = None;
+                Some((None, ComposeStatus::Composed))
+            }
+            ffi::xkb_compose_status::XKB_COMPOSE_CANCELLED => {
+                self.compose_pending = None;
+                Some((None, ComposeStatus::Cancelled))
+            }
+            // The sequence was reset, like with `Window::reset_dead_keys`.
+            ffi::xkb_compose_status::XKB_COMPOSE_NOTHING => self
+                .compose_pending
+                .take()
+                .map(|_| (None, ComposeStatus::Cancelled)),
+        };
+    }
+
     fn keysym_to_utf8_raw(&mut self, keysym: u32) -> Option<SmolStr> {
         self.scratch_buffer.clear();
         self.scratch_buffer.reserve(8);
@@ -466,6 +652,134 @@ impl Drop for KbdState {
     }
 }
 
+/// A copy of a keymap, to look up the keys outside of the event loop.
+///
+/// The keymap is compiled again in its own context, since the reference counts of libxkbcommon
+/// aren't atomic.
+#[derive(Debug)]
+pub struct XkbKeymap {
+    text: String,
+    inner: Mutex<XkbKeymapInner>,
+}
+
+#[derive(Debug)]
+struct XkbKeymapInner {
+    xkb_context: *mut ffi::xkb_context,
+    xkb_keymap: *mut ffi::xkb_keymap,
+}
+
+// SAFETY: The context and the keymap aren't shared with anything else, and they're only used
+// behind the mutex.
+unsafe impl Send for XkbKeymapInner {}
+
+impl XkbKeymap {
+    /// Compile the keymap from its text.
+    pub fn from_string(text: String) -> Option<Self> {
+        ffi::xkbcommon_option()?;
+        let c_text = CString::new(text.as_str()).ok()?;
+
+        unsafe {
+            let xkb_context = (XKBH.xkb_context_new)(ffi::xkb_context_flags::XKB_CONTEXT_NO_FLAGS);
+            if xkb_context.is_null() {
+                return None;
+            }
+
+            let xkb_keymap = (XKBH.xkb_keymap_new_from_string)(
+                xkb_context,
+                c_text.as_ptr(),
+                ffi::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+                ffi::xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            );
+            if xkb_keymap.is_null() {
+                (XKBH.xkb_context_unref)(xkb_context);
+                return None;
+            }
+
+            Some(Self {
+                text,
+                inner: Mutex::new(XkbKeymapInner {
+                    xkb_context,
+                    xkb_keymap,
+                }),
+            })
+        }
+    }
+
+    /// The key produced by the physical key in the layout, with the modifiers held.
+    pub fn key_for_physical(
+        &self,
+        layout: usize,
+        physical_key: PhysicalKey,
+        modifiers: crate::keyboard::ModifiersState,
+    ) -> Key {
+        let keycode = match keymap::physicalkey_to_scancode(physical_key) {
+            Some(scancode) => scancode + 8,
+            None => return Key::Unidentified(NativeKey::Unidentified),
+        };
+
+        let inner = self.inner.lock().unwrap();
+        let keysym = unsafe {
+            let xkb_state = (XKBH.xkb_state_new)(inner.xkb_keymap);
+            if xkb_state.is_null() {
+                return Key::Unidentified(NativeKey::Unidentified);
+            }
+
+            let mut depressed = 0;
+            for (modifier, name) in [
+                (
+                    crate::keyboard::ModifiersState::SHIFT,
+                    ffi::XKB_MOD_NAME_SHIFT,
+                ),
+                (
+                    crate::keyboard::ModifiersState::CONTROL,
+                    ffi::XKB_MOD_NAME_CTRL,
+                ),
+                (crate::keyboard::ModifiersState::ALT, ffi::XKB_MOD_NAME_ALT),
+                (
+                    crate::keyboard::ModifiersState::SUPER,
+                    ffi::XKB_MOD_NAME_LOGO,
+                ),
+            ] {
+                if !modifiers.contains(modifier) {
+                    continue;
+                }
+
+                let index = (XKBH.xkb_keymap_mod_get_index)(inner.xkb_keymap, name.as_ptr().cast());
+                if index != ffi::XKB_MOD_INVALID {
+                    depressed |= 1 << index;
+                }
+            }
+
+            (XKBH.xkb_state_update_mask)(xkb_state, depressed, 0, 0, 0, 0, layout as u32);
+            let keysym = (XKBH.xkb_state_key_get_one_sym)(xkb_state, keycode);
+            (XKBH.xkb_state_unref)(xkb_state);
+            keysym
+        };
+
+        match keymap::keysym_to_key(keysym) {
+            key @ Key::Unidentified(_) => keysym_to_utf8(keysym).map(Key::Character).unwrap_or(key),
+            key => key,
+        }
+    }
+}
+
+impl PartialEq for XkbKeymap {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for XkbKeymap {}
+
+impl Drop for XkbKeymapInner {
+    fn drop(&mut self) {
+        unsafe {
+            (XKBH.xkb_keymap_unref)(self.xkb_keymap);
+            (XKBH.xkb_context_unref)(self.xkb_context);
+        }
+    }
+}
+
 struct KeyEventResults<'a> {
     state: &'a mut KbdState,
     keycode: u32,
@@ -636,19 +950,25 @@ impl ModifiersState {
     }
 
     fn update_with(&mut self, state: *mut ffi::xkb_state) {
-        let mod_name_is_active = |mod_name: &[u8]| unsafe {
+        let mod_name_is_active = |mod_name: &[u8], component| unsafe {
             (XKBH.xkb_state_mod_name_is_active)(
                 state,
                 mod_name.as_ptr() as *const c_char,
-                xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+                component,
             ) > 0
         };
-        self.ctrl = mod_name_is_active(ffi::XKB_MOD_NAME_CTRL);
-        self.alt = mod_name_is_active(ffi::XKB_MOD_NAME_ALT);
-        self.shift = mod_name_is_active(ffi::XKB_MOD_NAME_SHIFT);
-        self.caps_lock = mod_name_is_active(ffi::XKB_MOD_NAME_CAPS);
-        self.logo = mod_name_is_active(ffi::XKB_MOD_NAME_LOGO);
-        self.num_lock = mod_name_is_active(ffi::XKB_MOD_NAME_NUM);
+        let is_active = |mod_name: &[u8]| {
+            mod_name_is_active(mod_name, xkb_state_component::XKB_STATE_MODS_EFFECTIVE)
+        };
+        let is_locked = |mod_name: &[u8]| {
+            mod_name_is_active(mod_name, xkb_state_component::XKB_STATE_MODS_LOCKED)
+        };
+        self.ctrl = is_active(ffi::XKB_MOD_NAME_CTRL);
+        self.alt = is_active(ffi::XKB_MOD_NAME_ALT);
+        self.shift = is_active(ffi::XKB_MOD_NAME_SHIFT);
+        self.caps_lock = is_locked(ffi::XKB_MOD_NAME_CAPS);
+        self.logo = is_active(ffi::XKB_MOD_NAME_LOGO);
+        self.num_lock = is_locked(ffi::XKB_MOD_NAME_NUM);
     }
 }
 
@@ -663,6 +983,25 @@ impl From<ModifiersState> for crate::keyboard::ModifiersState {
     }
 }
 
+impl From<ModifiersState> for LockState {
+    fn from(mods: ModifiersState) -> LockState {
+        let mut lock_state = LockState::empty();
+        lock_state.set(LockState::CAPS_LOCK, mods.caps_lock);
+        lock_state.set(LockState::NUM_LOCK, mods.num_lock);
+        lock_state
+    }
+}
+
+impl From<ModifiersState> for crate::event::Modifiers {
+    fn from(mods: ModifiersState) -> crate::event::Modifiers {
+        crate::event::Modifiers {
+            state: mods.into(),
+            lock_state: mods.into(),
+            pressed_mods: Default::default(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     /// libxkbcommon is not available
@@ -676,6 +1015,87 @@ enum XkbCompose {
     Uninitialized,
 }
 
+/// Parse the names of the layouts, the `name[GroupN]` of the symbols, from the keymap text.
+fn parse_layout_names(keymap: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let group_names = keymap.lines().filter_map(|line| {
+        let (index, name) = line.trim().strip_prefix("name[Group")?.split_once("]=")?;
+        let index = index.parse::<usize>().ok()?.checked_sub(1)?;
+        Some((index, name.trim_end_matches(';').trim_matches('"')))
+    });
+    for (index, name) in group_names {
+        if names.len() <= index {
+            names.resize(index + 1, String::new());
+        }
+        names[index] = name.to_owned();
+    }
+
+    names
+}
+
+/// Compile the keymap set by the user.
+unsafe fn compile_keymap(
+    context: *mut ffi::xkb_context,
+    source: &KeymapSource,
+) -> *mut ffi::xkb_keymap {
+    match source {
+        KeymapSource::Names(names) => {
+            let to_c_string =
+                |name: &Option<String>| name.as_deref().and_then(|name| CString::new(name).ok());
+            let rules = to_c_string(&names.rules);
+            let model = to_c_string(&names.model);
+            let layout = to_c_string(&names.layout);
+            let variant = to_c_string(&names.variant);
+            let options = to_c_string(&names.options);
+
+            // The names which aren't set are null, so libxkbcommon uses its defaults.
+            let as_ptr =
+                |name: &Option<CString>| name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+            let names = ffi::xkb_rule_names {
+                rules: as_ptr(&rules),
+                model: as_ptr(&model),
+                layout: as_ptr(&layout),
+                variant: as_ptr(&variant),
+                options: as_ptr(&options),
+            };
+
+            unsafe {
+                (XKBH.xkb_keymap_new_from_names)(
+                    context,
+                    &names,
+                    ffi::xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+                )
+            }
+        }
+        KeymapSource::String(text) => match CString::new(text.as_str()) {
+            Ok(text) => unsafe {
+                (XKBH.xkb_keymap_new_from_string)(
+                    context,
+                    text.as_ptr(),
+                    ffi::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+                    ffi::xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+                )
+            },
+            Err(_) => ptr::null_mut(),
+        },
+    }
+}
+
+/// Convert the keysym to UTF-8, without a scratch buffer.
+fn keysym_to_utf8(keysym: u32) -> Option<SmolStr> {
+    // The longest UTF-8 character, with the null-terminator, fits in the 7 bytes recommended by
+    // libxkbcommon.
+    let mut buffer = [0u8; 8];
+    let bytes_written =
+        unsafe { (XKBH.xkb_keysym_to_utf8)(keysym, buffer.as_mut_ptr().cast(), buffer.len()) };
+    if bytes_written <= 0 {
+        return None;
+    }
+
+    // Remove the null-terminator
+    byte_slice_to_smol_str(&buffer[..bytes_written as usize - 1])
+}
+
 // Note: This is track_caller so we can have more informative line numbers when logging
 #[track_caller]
 fn byte_slice_to_smol_str(bytes: &[u8]) -> Option<SmolStr> {
@@ -689,3 +1109,196 @@ fn byte_slice_to_smol_str(bytes: &[u8]) -> Option<SmolStr> {
         })
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A keymap with a US and a French layout, only defining the keys of the tests.
+    const TEST_KEYMAP: &str = r#"xkb_keymap {
+        xkb_keycodes {
+            minimum = 8;
+            maximum = 255;
+            <ESC> = 9;
+            <AD01> = 24;
+            <LFSH> = 50;
+            <LCTL> = 37;
+        };
+        xkb_types {
+            type "ONE_LEVEL" {
+                modifiers = none;
+                level_name[Level1] = "Any";
+            };
+            type "ALPHABETIC" {
+                modifiers = Shift;
+                map[Shift] = Level2;
+                level_name[Level1] = "Base";
+                level_name[Level2] = "Caps";
+            };
+        };
+        xkb_compatibility {
+        };
+        xkb_symbols {
+            name[Group1] = "English (US)";
+            name[Group2] = "French (AZERTY)";
+            key <ESC> { [ Escape ] };
+            key <AD01> { symbols[Group1] = [ q, Q ], symbols[Group2] = [ a, A ] };
+            modifier_map Shift { <LFSH> };
+            modifier_map Control { <LCTL> };
+        };
+    };"#;
+
+    #[test]
+    fn layout_names() {
+        let keymap = "xkb_symbols \"pc+us+fr:2\" {\n\
+                      \tname[Group1]=\"English (US)\";\n\
+                      \tname[Group2]=\"French (AZERTY)\";\n\
+                      \tkey <ESC> { [ Escape ] };\n\
+                      };";
+        assert_eq!(
+            parse_layout_names(keymap),
+            vec!["English (US)".to_owned(), "French (AZERTY)".to_owned()]
+        );
+    }
+
+    #[test]
+    fn key_for_physical() {
+        use crate::keyboard::{KeyCode, ModifiersState, NamedKey};
+
+        // Skip the test when libxkbcommon isn't available.
+        let keymap = match XkbKeymap::from_string(TEST_KEYMAP.to_owned()) {
+            Some(keymap) => keymap,
+            None if ffi::xkbcommon_option().is_none() => return,
+            None => panic!("failed to compile the keymap"),
+        };
+
+        let key_q = PhysicalKey::Code(KeyCode::KeyQ);
+        let none = ModifiersState::empty();
+        assert_eq!(
+            keymap.key_for_physical(0, key_q, none),
+            Key::Character("q".into())
+        );
+        assert_eq!(
+            keymap.key_for_physical(1, key_q, none),
+            Key::Character("a".into())
+        );
+        assert_eq!(
+            keymap.key_for_physical(1, key_q, ModifiersState::SHIFT),
+            Key::Character("A".into())
+        );
+        assert_eq!(
+            keymap.key_for_physical(1, key_q, ModifiersState::CONTROL),
+            Key::Character("a".into())
+        );
+        assert_eq!(
+            keymap.key_for_physical(0, PhysicalKey::Code(KeyCode::Escape), none),
+            Key::Named(NamedKey::Escape)
+        );
+        assert_eq!(
+            keymap.key_for_physical(0, PhysicalKey::Code(KeyCode::KeyW), none),
+            Key::Unidentified(NativeKey::Unidentified)
+        );
+    }
+
+    #[test]
+    fn custom_keymap() {
+        // Skip the test when libxkbcommon isn't available.
+        let mut state = match KbdState::new() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        state.set_custom_keymap(KeymapSource::String(TEST_KEYMAP.to_owned()));
+        assert!(state.has_custom_keymap());
+
+        let event = state.process_key_event(24, ElementState::Pressed, false);
+        assert_eq!(event.logical_key, Key::Character("q".into()));
+        assert_eq!(event.text.as_deref(), Some("q"));
+
+        // Switch to the French layout.
+        state.update_modifiers(0, 0, 0, 0, 0, 1);
+        let event = state.process_key_event(24, ElementState::Pressed, false);
+        assert_eq!(event.logical_key, Key::Character("a".into()));
+
+        let info = state.keyboard_layout_info();
+        assert_eq!(info.active, 1);
+        assert_eq!(info.layouts[1].name, "French (AZERTY)");
+
+        // The keymap is shared with the virtual keyboards.
+        assert!(state.keymap_text().unwrap().contains("French (AZERTY)"));
+    }
+
+    #[test]
+    fn lock_state() {
+        use crate::keyboard::LockState;
+
+        // Skip the test when libxkbcommon isn't available.
+        let mut state = match KbdState::new() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        state.set_custom_keymap(KeymapSource::String(TEST_KEYMAP.to_owned()));
+
+        // Lock the `Lock` and `Mod2` real modifiers, the Caps Lock and Num Lock.
+        state.update_modifiers(0, 0, 0b10010, 0, 0, 0);
+        assert_eq!(
+            LockState::from(state.mods_state()),
+            LockState::CAPS_LOCK | LockState::NUM_LOCK
+        );
+
+        // A depressed modifier isn't locked.
+        state.update_modifiers(0b10, 0, 0, 0, 0, 0);
+        assert_eq!(LockState::from(state.mods_state()), LockState::empty());
+    }
+
+    #[test]
+    fn compose_updates() {
+        use crate::keyboard::NamedKey;
+        use ffi::xkb_compose_status::*;
+
+        // Skip the test when libxkbcommon isn't available.
+        let mut state = match KbdState::new() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+
+        state.update_compose(
+            Some(XkbCompose::Accepted(XKB_COMPOSE_COMPOSING)),
+            &Key::Dead(Some('´')),
+        );
+        assert_eq!(
+            state.take_compose_update(),
+            Some((Some("´".to_owned()), ComposeStatus::Composing))
+        );
+
+        // The modifiers don't interrupt the sequence.
+        state.update_compose(Some(XkbCompose::Ignored), &Key::Named(NamedKey::Shift));
+        assert_eq!(state.take_compose_update(), None);
+
+        state.update_compose(
+            Some(XkbCompose::Accepted(XKB_COMPOSE_COMPOSED)),
+            &Key::Character("é".into()),
+        );
+        assert_eq!(
+            state.take_compose_update(),
+            Some((None, ComposeStatus::Composed))
+        );
+
+        // A sequence interrupted by a reset is cancelled.
+        state.update_compose(
+            Some(XkbCompose::Accepted(XKB_COMPOSE_COMPOSING)),
+            &Key::Named(NamedKey::Compose),
+        );
+        assert_eq!(
+            state.take_compose_update(),
+            Some((Some(String::new()), ComposeStatus::Composing))
+        );
+        state.update_compose(
+            Some(XkbCompose::Accepted(XKB_COMPOSE_NOTHING)),
+            &Key::Character("e".into()),
+        );
+        assert_eq!(
+            state.take_compose_update(),
+            Some((None, ComposeStatus::Cancelled))
+        );
+    }
+}
@@ -18,19 +18,23 @@ use smol_str::SmolStr;
 use self::x11::{X11Error, XConnection, XError, XNotSupported};
 #[cfg(x11_platform)]
 use crate::platform::x11::{WindowType as XWindowType, XlibErrorHook};
+use crate::platform::xkb::KeymapSource;
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
     error::{EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError},
+    event::ElementState,
     event_loop::{
         AsyncRequestSerial, ControlFlow, DeviceEvents, EventLoopClosed,
         EventLoopWindowTarget as RootELW,
     },
     icon::Icon,
-    keyboard::Key,
+    keyboard::{Key, ModifiersState, NativeKey, PhysicalKey},
+    monitor::{GammaRamp, MonitorTransform},
     platform::pump_events::PumpStatus,
     window::{
-        ActivationToken, Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme,
-        UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+        AccentColor, ActivationToken, ContentType, Cursor, CursorGrabMode, ImeHints, ImePurpose,
+        InjectedPointer, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+        WindowAttributes, WindowButtons, WindowLevel,
     },
 };
 
@@ -53,10 +57,13 @@ pub(crate) enum Backend {
     Wayland,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    pub(crate) no_synthetic_key_repeat: bool,
+    pub(crate) xkb_keymap: Option<KeymapSource>,
+    pub(crate) no_xkb_compose: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,6 +84,8 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub activation_token: Option<ActivationToken>,
     #[cfg(x11_platform)]
     pub x11: X11WindowBuilderAttributes,
+    #[cfg(wayland_platform)]
+    pub wayland: WaylandWindowBuilderAttributes,
 }
 
 #[derive(Clone, Debug)]
@@ -88,10 +97,23 @@ pub struct X11WindowBuilderAttributes {
     pub override_redirect: bool,
     pub x11_window_types: Vec<XWindowType>,
 
+    /// The window this window is transient for.
+    pub transient_for: Option<x11rb::protocol::xproto::Window>,
+
     /// The parent window to embed this window into.
     pub embed_window: Option<x11rb::protocol::xproto::Window>,
 }
 
+#[derive(Clone, Debug, Default)]
+#[cfg(wayland_platform)]
+pub struct WaylandWindowBuilderAttributes {
+    /// The name of the window icon from the icon theme.
+    pub icon_name: Option<String>,
+
+    /// The handle of the foreign toplevel to be transient for.
+    pub parent_handle: Option<String>,
+}
+
 impl Default for PlatformSpecificWindowBuilderAttributes {
     fn default() -> Self {
         Self {
@@ -104,8 +126,11 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
                 base_size: None,
                 override_redirect: false,
                 x11_window_types: vec![XWindowType::Normal],
+                transient_for: None,
                 embed_window: None,
             },
+            #[cfg(wayland_platform)]
+            wayland: WaylandWindowBuilderAttributes::default(),
         }
     }
 }
@@ -139,7 +164,7 @@ pub(crate) enum Window {
     #[cfg(x11_platform)]
     X(x11::Window),
     #[cfg(wayland_platform)]
-    Wayland(wayland::Window),
+    Wayland(Box<wayland::Window>),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -237,6 +262,21 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.position())
     }
 
+    #[inline]
+    pub fn logical_position(&self) -> LogicalPosition<i32> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.logical_position())
+    }
+
+    #[inline]
+    pub fn logical_size(&self) -> LogicalSize<u32> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.logical_size())
+    }
+
+    #[inline]
+    pub fn transform(&self) -> MonitorTransform {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.transform())
+    }
+
     #[inline]
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         x11_or_wayland!(match self; MonitorHandle(m) => m.refresh_rate_millihertz())
@@ -247,6 +287,58 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.physical_size_mm())
+    }
+
+    #[inline]
+    pub fn manufacturer(&self) -> Option<String> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.manufacturer())
+    }
+
+    #[inline]
+    pub fn model(&self) -> Option<String> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.model())
+    }
+
+    #[inline]
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.edid())
+    }
+
+    pub fn gamma_ramp(&self) -> Result<GammaRamp, ExternalError> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(m) => {
+                let xconn = match X11_BACKEND.lock().unwrap().as_ref() {
+                    Ok(xconn) => xconn.clone(),
+                    Err(_) => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+                };
+                m.gamma_ramp(&xconn)
+                    .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))
+            }
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(m) => m.gamma_ramp(),
+        }
+    }
+
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), ExternalError> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(m) => {
+                let xconn = match X11_BACKEND.lock().unwrap().as_ref() {
+                    Ok(xconn) => xconn.clone(),
+                    Err(_) => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+                };
+                m.set_gamma_ramp(&xconn, ramp)
+                    .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))
+            }
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(m) => m.set_gamma_ramp(ramp),
+        }
+    }
+
     #[inline]
     pub fn video_modes(&self) -> Box<dyn Iterator<Item = VideoModeHandle>> {
         x11_or_wayland!(match self; MonitorHandle(m) => Box::new(m.video_modes()))
@@ -292,7 +384,8 @@ impl Window {
         match *window_target {
             #[cfg(wayland_platform)]
             EventLoopWindowTarget::Wayland(ref window_target) => {
-                wayland::Window::new(window_target, attribs).map(Window::Wayland)
+                wayland::Window::new(window_target, attribs)
+                    .map(|window| Window::Wayland(Box::new(window)))
             }
             #[cfg(x11_platform)]
             EventLoopWindowTarget::X(ref window_target) => {
@@ -449,6 +542,16 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor_hittest(hittest))
     }
 
+    #[inline]
+    pub fn inject_key(&self, key: PhysicalKey, state: ElementState) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(w) => w.inject_key(key, state))
+    }
+
+    #[inline]
+    pub fn inject_pointer(&self, pointer: InjectedPointer) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(w) => w.inject_pointer(pointer))
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; Window(w) => w.scale_factor())
@@ -509,6 +612,36 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_window_icon(window_icon.map(|icon| icon.inner)))
     }
 
+    #[cfg(wayland_platform)]
+    #[inline]
+    pub fn set_synthetic_key_repeat(&self, synthetic_key_repeat: bool) {
+        match self {
+            #[cfg(x11_platform)]
+            Window::X(_) => (),
+            Window::Wayland(window) => window.set_synthetic_key_repeat(synthetic_key_repeat),
+        }
+    }
+
+    #[cfg(wayland_platform)]
+    #[inline]
+    pub fn set_icon_name(&self, icon_name: Option<String>) {
+        match self {
+            #[cfg(x11_platform)]
+            Window::X(_) => (),
+            Window::Wayland(window) => window.set_icon_name(icon_name),
+        }
+    }
+
+    #[cfg(wayland_platform)]
+    #[inline]
+    pub fn export_handle(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
+        match self {
+            #[cfg(x11_platform)]
+            Window::X(_) => Err(NotSupportedError::new()),
+            Window::Wayland(window) => window.export_handle(),
+        }
+    }
+
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
         x11_or_wayland!(match self; Window(w) => w.set_ime_cursor_area(position, size))
@@ -529,6 +662,16 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_ime_purpose(purpose))
     }
 
+    #[inline]
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        x11_or_wayland!(match self; Window(w) => w.set_ime_hints(hints))
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        x11_or_wayland!(match self; Window(w) => w.set_ime_surrounding_text(text, cursor, anchor))
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         x11_or_wayland!(match self; Window(w) => w.focus_window())
@@ -542,6 +685,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.request_redraw())
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        x11_or_wayland!(match self; Window(w) => w.request_frame())
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         x11_or_wayland!(match self; Window(w) => w.pre_present_notify())
@@ -617,10 +765,25 @@ impl Window {
         x11_or_wayland!(match self; Window(window) => window.theme())
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        x11_or_wayland!(match self; Window(window) => window.accent_color())
+    }
+
     pub fn set_content_protected(&self, protected: bool) {
         x11_or_wayland!(match self; Window(window) => window.set_content_protected(protected))
     }
 
+    #[inline]
+    pub fn set_presentation_hint(&self, hint: PresentationHint) {
+        x11_or_wayland!(match self; Window(window) => window.set_presentation_hint(hint))
+    }
+
+    #[inline]
+    pub fn set_content_type(&self, content_type: ContentType) {
+        x11_or_wayland!(match self; Window(window) => window.set_content_type(content_type))
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         x11_or_wayland!(match self; Window(window) => window.has_focus())
@@ -637,6 +800,25 @@ pub struct KeyEventExtra {
     pub key_without_modifiers: Key,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfoExtra {
+    pub keymap: Option<Arc<common::xkb_state::XkbKeymap>>,
+}
+
+impl KeyboardLayoutInfoExtra {
+    pub fn key_for_physical(
+        &self,
+        layout: usize,
+        physical_key: PhysicalKey,
+        modifiers: ModifiersState,
+    ) -> Key {
+        match self.keymap.as_ref() {
+            Some(keymap) => keymap.key_for_physical(layout, physical_key, modifiers),
+            None => Key::Unidentified(NativeKey::Unidentified),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub(crate) enum PlatformCustomCursor {
     #[cfg(wayland_platform)]
@@ -775,25 +957,46 @@ impl<T: 'static> EventLoop<T> {
         // Create the display based on the backend.
         match backend {
             #[cfg(wayland_platform)]
-            Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
+            Backend::Wayland => EventLoop::new_wayland_any_thread(
+                !attributes.no_synthetic_key_repeat,
+                attributes.xkb_keymap.clone(),
+                !attributes.no_xkb_compose,
+            )
+            .map_err(Into::into),
             #[cfg(x11_platform)]
-            Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
+            Backend::X => EventLoop::new_x11_any_thread(
+                attributes.xkb_keymap.clone(),
+                !attributes.no_xkb_compose,
+            )
+            .map_err(Into::into),
         }
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop<T>, EventLoopError> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        synthetic_key_repeat: bool,
+        xkb_keymap: Option<KeymapSource>,
+        xkb_compose: bool,
+    ) -> Result<EventLoop<T>, EventLoopError> {
+        wayland::EventLoop::new(synthetic_key_repeat, xkb_keymap, xkb_compose)
+            .map(|evlp| EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
-    fn new_x11_any_thread() -> Result<EventLoop<T>, EventLoopError> {
+    fn new_x11_any_thread(
+        xkb_keymap: Option<KeymapSource>,
+        xkb_compose: bool,
+    ) -> Result<EventLoop<T>, EventLoopError> {
         let xconn = match X11_BACKEND.lock().unwrap().as_ref() {
             Ok(xconn) => xconn.clone(),
             Err(_) => return Err(EventLoopError::NotSupported(NotSupportedError::new())),
         };
 
-        Ok(EventLoop::X(x11::EventLoop::new(xconn)))
+        Ok(EventLoop::X(x11::EventLoop::new(
+            xconn,
+            xkb_keymap,
+            xkb_compose,
+        )))
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
@@ -862,6 +1065,70 @@ impl EventLoopWindowTarget {
         }
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        x11_or_wayland!(match self; Self(evlp) => evlp.system_settings())
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        x11_or_wayland!(match self; Self(evlp) => evlp.keyboard_layouts())
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, index: usize) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Self(evlp) => evlp.set_keyboard_layout(index))
+    }
+
+    #[cfg(wayland_platform)]
+    #[inline]
+    pub fn repeat_info(&self) -> Option<crate::keyboard::RepeatInfo> {
+        match *self {
+            EventLoopWindowTarget::Wayland(ref evlp) => evlp.repeat_info(),
+            #[cfg(x11_platform)]
+            EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn xsettings(&self) -> crate::platform::x11::XSettings {
+        match *self {
+            #[cfg(wayland_platform)]
+            EventLoopWindowTarget::Wayland(_) => Default::default(),
+            #[cfg(x11_platform)]
+            EventLoopWindowTarget::X(ref evlp) => evlp.x_connection().xsettings().to_public(),
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: PhysicalKey,
+    ) -> Result<crate::event::HotkeyId, ExternalError> {
+        match *self {
+            #[cfg(wayland_platform)]
+            EventLoopWindowTarget::Wayland(_) => {
+                Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+            #[cfg(x11_platform)]
+            EventLoopWindowTarget::X(ref evlp) => evlp.register_global_hotkey(modifiers, key),
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn unregister_global_hotkey(&self, id: crate::event::HotkeyId) {
+        match *self {
+            #[cfg(wayland_platform)]
+            EventLoopWindowTarget::Wayland(_) => (),
+            #[cfg(x11_platform)]
+            EventLoopWindowTarget::X(ref evlp) => evlp.unregister_global_hotkey(id),
+        }
+    }
+
     #[inline]
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         match *self {
@@ -23,6 +23,7 @@ use crate::event_loop::{
     ControlFlow, DeviceEvents, EventLoopWindowTarget as RootEventLoopWindowTarget,
 };
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::xkb::KeymapSource;
 use crate::platform_impl::platform::min_timeout;
 use crate::platform_impl::{EventLoopWindowTarget as PlatformEventLoopWindowTarget, OsError};
 
@@ -34,7 +35,7 @@ use sink::EventSink;
 
 use super::state::{WindowCompositorUpdate, WinitState};
 use super::window::state::FrameCallbackState;
-use super::{logical_to_physical_rounded, DeviceId, WaylandError, WindowId};
+use super::{DeviceId, WaylandError, WindowId};
 
 type WaylandDispatcher = calloop::Dispatcher<'static, WaylandSource<WinitState>, WinitState>;
 
@@ -71,7 +72,11 @@ pub struct EventLoop<T: 'static> {
 }
 
 impl<T: 'static> EventLoop<T> {
-    pub fn new() -> Result<EventLoop<T>, EventLoopError> {
+    pub fn new(
+        synthetic_key_repeat: bool,
+        xkb_keymap: Option<KeymapSource>,
+        xkb_compose: bool,
+    ) -> Result<EventLoop<T>, EventLoopError> {
         macro_rules! map_err {
             ($e:expr, $err:expr) => {
                 $e.map_err(|error| os_error!($err(error).into()))
@@ -93,6 +98,9 @@ impl<T: 'static> EventLoop<T> {
 
         let mut winit_state = WinitState::new(&globals, &queue_handle, event_loop.handle())
             .map_err(|error| os_error!(error))?;
+        winit_state.synthetic_key_repeat = synthetic_key_repeat;
+        winit_state.xkb_keymap = xkb_keymap;
+        winit_state.xkb_compose = xkb_compose;
 
         // NOTE: do a roundtrip after binding the globals to prevent potential
         // races with the server.
@@ -360,7 +368,7 @@ impl<T: 'static> EventLoop<T> {
                     let windows = state.windows.get_mut();
                     let window = windows.get(&window_id).unwrap().lock().unwrap();
                     let scale_factor = window.scale_factor();
-                    let size = logical_to_physical_rounded(window.inner_size(), scale_factor);
+                    let size = window.physical_inner_size();
                     (size, scale_factor)
                 });
 
@@ -407,8 +415,7 @@ impl<T: 'static> EventLoop<T> {
                     let windows = state.windows.get_mut();
                     let window = windows.get(&window_id).unwrap().lock().unwrap();
 
-                    let scale_factor = window.scale_factor();
-                    let size = logical_to_physical_rounded(window.inner_size(), scale_factor);
+                    let size = window.physical_inner_size();
 
                     // Mark the window as needed a redraw.
                     state
@@ -488,6 +495,7 @@ impl<T: 'static> EventLoop<T> {
 
                 // Reset the frame callbacks state.
                 window.frame_callback_reset();
+                let frame_time = window.take_frame_time();
                 let mut redraw_requested = window_requests
                     .get(&window_id)
                     .unwrap()
@@ -496,7 +504,7 @@ impl<T: 'static> EventLoop<T> {
                 // Redraw the frame while at it.
                 redraw_requested |= window.refresh_frame();
 
-                redraw_requested.then_some(WindowEvent::RedrawRequested)
+                redraw_requested.then_some(WindowEvent::RedrawRequested { frame_time })
             });
 
             if let Some(event) = event {
@@ -654,6 +662,28 @@ impl EventLoopWindowTarget {
         self.exit.get()
     }
 
+    #[inline]
+    pub fn repeat_info(&self) -> Option<crate::keyboard::RepeatInfo> {
+        self.state.borrow().repeat_info()
+    }
+
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        self.state.borrow().system_settings()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        self.state.borrow().keyboard_layouts()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
@@ -3,6 +3,7 @@
 use std::vec::Drain;
 
 use crate::event::{DeviceEvent, DeviceId as RootDeviceId, Event, WindowEvent};
+use crate::event_loop::SystemSettings;
 use crate::platform_impl::platform::DeviceId as PlatformDeviceId;
 use crate::window::WindowId as RootWindowId;
 
@@ -44,6 +45,18 @@ impl EventSink {
         });
     }
 
+    /// Add new monitor event to a queue.
+    #[inline]
+    pub fn push_monitor_event(&mut self, event: Event<()>) {
+        self.window_events.push(event);
+    }
+
+    /// Add new system settings event to a queue.
+    #[inline]
+    pub fn push_system_settings_event(&mut self, settings: SystemSettings) {
+        self.window_events.push(Event::SystemSettingsChanged(settings));
+    }
+
     #[inline]
     pub fn append(&mut self, other: &mut Self) {
         self.window_events.append(&mut other.window_events);
@@ -18,6 +18,7 @@ pub use window::Window;
 
 mod event_loop;
 mod output;
+mod protocols;
 mod seat;
 mod state;
 mod types;
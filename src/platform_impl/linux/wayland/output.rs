@@ -1,21 +1,25 @@
-use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_output::{Transform, WlOutput};
 use sctk::reexports::client::Proxy;
 
 use sctk::output::OutputData;
 
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+use crate::error::{ExternalError, NotSupportedError};
+use crate::monitor::{GammaRamp, MonitorTransform};
 use crate::platform_impl::platform::VideoModeHandle as PlatformVideoModeHandle;
 
 use super::event_loop::EventLoopWindowTarget;
+use super::types::wlr_gamma_control::GammaControlManager;
 
 impl EventLoopWindowTarget {
     #[inline]
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
-        self.state
-            .borrow()
+        let state = self.state.borrow();
+        let gamma_control_manager = state.gamma_control_manager.clone();
+        state
             .output_state
             .outputs()
-            .map(MonitorHandle::new)
+            .map(move |output| MonitorHandle::new(output, gamma_control_manager.clone()))
     }
 
     #[inline]
@@ -25,15 +29,55 @@ impl EventLoopWindowTarget {
     }
 }
 
+/// The attributes of an output reported by the monitor handle.
+#[derive(Debug, PartialEq)]
+pub struct MonitorInfo {
+    name: Option<String>,
+    make: String,
+    model: String,
+    location: (i32, i32),
+    logical_position: Option<(i32, i32)>,
+    logical_size: Option<(i32, i32)>,
+    physical_size: (i32, i32),
+    transform: Transform,
+    scale_factor: i32,
+    modes: Vec<((i32, i32), i32, bool)>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MonitorHandle {
     pub(crate) proxy: WlOutput,
+    gamma_control_manager: Option<GammaControlManager>,
 }
 
 impl MonitorHandle {
     #[inline]
-    pub(crate) fn new(proxy: WlOutput) -> Self {
-        Self { proxy }
+    pub(crate) fn new(proxy: WlOutput, gamma_control_manager: Option<GammaControlManager>) -> Self {
+        Self {
+            proxy,
+            gamma_control_manager,
+        }
+    }
+
+    /// The current state of the output, to detect its changes.
+    pub fn info(&self) -> MonitorInfo {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| MonitorInfo {
+            name: info.name.clone(),
+            make: info.make.clone(),
+            model: info.model.clone(),
+            location: info.location,
+            logical_position: info.logical_position,
+            logical_size: info.logical_size,
+            physical_size: info.physical_size,
+            transform: info.transform,
+            scale_factor: info.scale_factor,
+            modes: info
+                .modes
+                .iter()
+                .map(|mode| (mode.dimensions, mode.refresh_rate, mode.current))
+                .collect(),
+        })
     }
 
     #[inline]
@@ -57,13 +101,49 @@ impl MonitorHandle {
                 .find_map(|mode| mode.current.then_some(mode.dimensions))
         });
 
+        // The modes are reported before the transform is applied.
         match dimensions {
+            Some((width, height)) if self.transform().swaps_dimensions() => {
+                (height as u32, width as u32)
+            }
             Some((width, height)) => (width as u32, height as u32),
             _ => (0, 0),
         }
         .into()
     }
 
+    #[inline]
+    pub fn logical_position(&self) -> LogicalPosition<i32> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data
+            .with_output_info(|info| info.logical_position.unwrap_or(info.location))
+            .into()
+    }
+
+    #[inline]
+    pub fn logical_size(&self) -> LogicalSize<u32> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        match output_data.with_output_info(|info| info.logical_size) {
+            Some((width, height)) => (width as u32, height as u32).into(),
+            None => self.size().to_logical(self.scale_factor() as f64),
+        }
+    }
+
+    #[inline]
+    pub fn transform(&self) -> MonitorTransform {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        match output_data.with_output_info(|info| info.transform) {
+            Transform::_90 => MonitorTransform::Rotate90,
+            Transform::_180 => MonitorTransform::Rotate180,
+            Transform::_270 => MonitorTransform::Rotate270,
+            Transform::Flipped => MonitorTransform::Flipped,
+            Transform::Flipped90 => MonitorTransform::Flipped90,
+            Transform::Flipped180 => MonitorTransform::Flipped180,
+            Transform::Flipped270 => MonitorTransform::Flipped270,
+            _ => MonitorTransform::Normal,
+        }
+    }
+
     #[inline]
     pub fn position(&self) -> PhysicalPosition<i32> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -97,6 +177,49 @@ impl MonitorHandle {
         output_data.scale_factor()
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        let (width, height) = output_data.with_output_info(|info| info.physical_size);
+        (width > 0 && height > 0).then_some((width as u32, height as u32))
+    }
+
+    #[inline]
+    pub fn manufacturer(&self) -> Option<String> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        let make = output_data.with_output_info(|info| info.make.clone());
+        (!make.is_empty()).then_some(make)
+    }
+
+    #[inline]
+    pub fn model(&self) -> Option<String> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        let model = output_data.with_output_info(|info| info.model.clone());
+        (!model.is_empty()).then_some(model)
+    }
+
+    #[inline]
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        // The EDID isn't exposed to the clients.
+        None
+    }
+
+    #[inline]
+    pub fn gamma_ramp(&self) -> Result<GammaRamp, ExternalError> {
+        match self.gamma_control_manager.as_ref() {
+            Some(manager) => manager.gamma_ramp(&self.proxy),
+            None => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), ExternalError> {
+        match self.gamma_control_manager.as_ref() {
+            Some(manager) => manager.set_gamma_ramp(&self.proxy, ramp),
+            None => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = PlatformVideoModeHandle> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
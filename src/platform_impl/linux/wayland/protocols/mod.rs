@@ -0,0 +1,43 @@
+//! Bindings for the Wayland protocols not yet shipped by `wayland-protocols`.
+
+#![allow(dead_code, non_camel_case_types, unused_unsafe, unused_variables)]
+#![allow(non_upper_case_globals, non_snake_case, unused_imports)]
+#![allow(missing_docs, clippy::all)]
+
+/// The `xdg-toplevel-icon-v1` staging protocol.
+pub mod xdg_toplevel_icon {
+    use sctk::reexports::client as wayland_client;
+    use sctk::reexports::client::protocol::*;
+    use sctk::reexports::protocols::xdg::shell::client::*;
+
+    pub mod __interfaces {
+        use sctk::reexports::client::protocol::__interfaces::*;
+        use sctk::reexports::protocols::xdg::shell::client::__interfaces::*;
+        wayland_scanner::generate_interfaces!(
+            "src/platform_impl/linux/wayland/protocols/xdg-toplevel-icon-v1.xml"
+        );
+    }
+    use self::__interfaces::*;
+
+    wayland_scanner::generate_client_code!(
+        "src/platform_impl/linux/wayland/protocols/xdg-toplevel-icon-v1.xml"
+    );
+}
+
+/// The `virtual-keyboard-unstable-v1` protocol.
+pub mod virtual_keyboard {
+    use sctk::reexports::client as wayland_client;
+    use sctk::reexports::client::protocol::*;
+
+    pub mod __interfaces {
+        use sctk::reexports::client::protocol::__interfaces::*;
+        wayland_scanner::generate_interfaces!(
+            "src/platform_impl/linux/wayland/protocols/virtual-keyboard-unstable-v1.xml"
+        );
+    }
+    use self::__interfaces::*;
+
+    wayland_scanner::generate_client_code!(
+        "src/platform_impl/linux/wayland/protocols/virtual-keyboard-unstable-v1.xml"
+    );
+}
@@ -1,8 +1,9 @@
 //! The keyboard input handling.
 
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use ahash::AHashMap;
 use calloop::timer::{TimeoutAction, Timer};
 use calloop::{LoopHandle, RegistrationToken};
 use log::warn;
@@ -15,12 +16,14 @@ use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 
 use crate::event::{ElementState, WindowEvent};
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{KeyboardLayoutInfo, ModifiersState};
+use crate::platform::xkb::KeymapSource;
 
 use crate::platform_impl::common::xkb_state::KbdState;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::seat::WinitSeatState;
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::window::WindowState;
 use crate::platform_impl::wayland::{self, DeviceId, WindowId};
 
 impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
@@ -43,14 +46,17 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     WlKeymapFormat::NoKeymap => {
                         warn!("non-xkb compatible keymap")
                     }
-                    WlKeymapFormat::XkbV1 => unsafe {
-                        seat_state
-                            .keyboard_state
-                            .as_mut()
-                            .unwrap()
-                            .xkb_state
-                            .init_with_fd(fd, size as usize);
-                    },
+                    WlKeymapFormat::XkbV1 => {
+                        let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+                        unsafe { keyboard_state.xkb_state.init_with_fd(fd, size as usize) };
+                        if let (Some(virtual_input_manager), Some(keymap)) = (
+                            state.virtual_input_manager.as_ref(),
+                            keyboard_state.xkb_state.keymap_text(),
+                        ) {
+                            virtual_input_manager.set_keymap(&data.seat, keymap);
+                        }
+                        update_keyboard_layouts(keyboard_state, &mut state.events_sink, data, true);
+                    }
                     _ => unreachable!(),
                 },
                 WEnum::Unknown(value) => {
@@ -83,7 +89,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                        WindowEvent::ModifiersChanged(seat_state.modifiers),
                         window_id,
                     );
                 }
@@ -137,6 +143,17 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 );
 
                 let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+
+                // The repeat could be disabled by the focused window, which also stops the
+                // repeat of the previous key.
+                if !synthetic_key_repeat(state.windows.get_mut(), data) {
+                    keyboard_state.current_repeat = None;
+                    if let Some(token) = keyboard_state.repeat_token.take() {
+                        keyboard_state.loop_handle.remove(token);
+                    }
+                    return;
+                }
+
                 let delay = match keyboard_state.repeat_info {
                     RepeatInfo::Repeat { delay, .. } => delay,
                     RepeatInfo::Disable => return,
@@ -165,6 +182,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                         let data = wl_keyboard.data::<KeyboardData>().unwrap();
                         let seat_state = state.seats.get_mut(&data.seat.id()).unwrap();
 
+                        // The window could disable the repeat while it is going.
+                        if !synthetic_key_repeat(state.windows.get_mut(), data) {
+                            let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+                            keyboard_state.current_repeat = None;
+                            keyboard_state.repeat_token = None;
+                            return TimeoutAction::Drop;
+                        }
+
                         // NOTE: The removed on event source is batched, but key change to
                         // `None` is instant.
                         let repeat_keycode =
@@ -224,9 +249,11 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 group,
                 ..
             } => {
-                let xkb_state = &mut seat_state.keyboard_state.as_mut().unwrap().xkb_state;
+                let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+                let xkb_state = &mut keyboard_state.xkb_state;
                 xkb_state.update_modifiers(mods_depressed, mods_latched, mods_locked, 0, 0, group);
                 seat_state.modifiers = xkb_state.mods_state().into();
+                update_keyboard_layouts(keyboard_state, &mut state.events_sink, data, false);
 
                 // HACK: part of the workaround from `WlKeyboardEvent::Enter`.
                 let window_id = match *data.window_id.lock().unwrap() {
@@ -238,12 +265,13 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 };
 
                 state.events_sink.push_window_event(
-                    WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                    WindowEvent::ModifiersChanged(seat_state.modifiers),
                     window_id,
                 );
             }
             WlKeyboardEvent::RepeatInfo { rate, delay } => {
                 let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+                let prev_repeat_info = keyboard_state.repeat_info;
                 keyboard_state.repeat_info = if rate == 0 {
                     // Stop the repeat once we get a disable event.
                     keyboard_state.current_repeat = None;
@@ -256,6 +284,11 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     let delay = Duration::from_millis(delay as u64);
                     RepeatInfo::Repeat { gap, delay }
                 };
+
+                if keyboard_state.repeat_info != prev_repeat_info {
+                    let settings = state.system_settings();
+                    state.events_sink.push_system_settings_event(settings);
+                }
             }
             _ => unreachable!(),
         }
@@ -282,17 +315,34 @@ pub struct KeyboardState {
 
     /// The current repeat raw key.
     pub current_repeat: Option<u32>,
+
+    /// The keyboard layouts last reported.
+    pub keyboard_layouts: KeyboardLayoutInfo,
 }
 
 impl KeyboardState {
-    pub fn new(keyboard: WlKeyboard, loop_handle: LoopHandle<'static, WinitState>) -> Self {
+    pub fn new(
+        keyboard: WlKeyboard,
+        loop_handle: LoopHandle<'static, WinitState>,
+        xkb_keymap: Option<KeymapSource>,
+        xkb_compose: bool,
+    ) -> Self {
+        let mut xkb_state = KbdState::new().unwrap();
+        if let Some(xkb_keymap) = xkb_keymap {
+            xkb_state.set_custom_keymap(xkb_keymap);
+        }
+        if !xkb_compose {
+            xkb_state.disable_compose();
+        }
+
         Self {
             keyboard,
             loop_handle,
-            xkb_state: KbdState::new().unwrap(),
+            xkb_state,
             repeat_info: RepeatInfo::default(),
             repeat_token: None,
             current_repeat: None,
+            keyboard_layouts: KeyboardLayoutInfo::default(),
         }
     }
 }
@@ -356,6 +406,48 @@ impl KeyboardData {
     }
 }
 
+/// Update the keyboard layouts, sending their change to the focused window.
+fn update_keyboard_layouts(
+    keyboard_state: &mut KeyboardState,
+    event_sink: &mut EventSink,
+    data: &KeyboardData,
+    keymap_changed: bool,
+) {
+    let keyboard_layouts = if keymap_changed {
+        keyboard_state.xkb_state.keyboard_layout_info()
+    } else {
+        KeyboardLayoutInfo {
+            active: keyboard_state.xkb_state.active_layout(),
+            ..keyboard_state.keyboard_layouts.clone()
+        }
+    };
+
+    if keyboard_layouts == keyboard_state.keyboard_layouts {
+        return;
+    }
+    keyboard_state.keyboard_layouts = keyboard_layouts.clone();
+
+    if let Some(window_id) = *data.window_id.lock().unwrap() {
+        event_sink.push_window_event(
+            WindowEvent::KeyboardLayoutChanged(keyboard_layouts),
+            window_id,
+        );
+    }
+}
+
+/// Whether the focused window of the keyboard wants the synthetic key repeat.
+fn synthetic_key_repeat(
+    windows: &AHashMap<WindowId, Arc<Mutex<WindowState>>>,
+    data: &KeyboardData,
+) -> bool {
+    match *data.window_id.lock().unwrap() {
+        Some(window_id) => windows
+            .get(&window_id)
+            .is_some_and(|window| window.lock().unwrap().synthetic_key_repeat()),
+        None => false,
+    }
+}
+
 fn key_input(
     seat_state: &mut WinitSeatState,
     event_sink: &mut EventSink,
@@ -372,10 +464,13 @@ fn key_input(
     let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
 
     let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId));
-    let event = keyboard_state
+    let mut event = keyboard_state
         .xkb_state
         .process_key_event(keycode, state, repeat);
 
+    // The repeats are synthesized with the timers.
+    event.is_generated = repeat;
+
     event_sink.push_window_event(
         WindowEvent::KeyboardInput {
             device_id,
@@ -384,4 +479,8 @@ fn key_input(
         },
         window_id,
     );
+
+    if let Some((pending, status)) = keyboard_state.xkb_state.take_compose_update() {
+        event_sink.push_window_event(WindowEvent::ComposeState { pending, status }, window_id);
+    }
 }
@@ -13,7 +13,8 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::
 use sctk::seat::pointer::ThemeSpec;
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 
-use crate::keyboard::ModifiersState;
+use crate::event::Modifiers;
+use crate::keyboard::KeyboardLayoutInfo;
 use crate::platform_impl::wayland::state::WinitState;
 
 mod keyboard;
@@ -22,10 +23,13 @@ mod tablet;
 mod text_input;
 mod touch;
 
+pub use keyboard::RepeatInfo;
 pub use pointer::relative_pointer::RelativePointerState;
-pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
+pub use pointer::{
+    winit_button_to_wayland, PointerConstraintsState, WinitPointerData, WinitPointerDataExt,
+};
 pub use tablet::{TabletPointer, TabletState};
-pub use text_input::{TextInputState, ZwpTextInputV3Ext};
+pub use text_input::{SurroundingText, TextInputState, ZwpTextInputV3Ext};
 
 use keyboard::{KeyboardData, KeyboardState};
 use text_input::TextInputData;
@@ -52,7 +56,7 @@ pub struct WinitSeatState {
     keyboard_state: Option<KeyboardState>,
 
     /// The current modifiers state on the seat.
-    modifiers: ModifiersState,
+    modifiers: Modifiers,
 
     /// Wether we have pending modifiers.
     modifiers_pending: bool,
@@ -62,6 +66,20 @@ impl WinitSeatState {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The key repeat of the keyboard, if the seat has one.
+    pub fn repeat_info(&self) -> Option<RepeatInfo> {
+        self.keyboard_state
+            .as_ref()
+            .map(|keyboard_state| keyboard_state.repeat_info)
+    }
+
+    /// The keyboard layouts of the keyboard, if the seat has one.
+    pub fn keyboard_layouts(&self) -> Option<&KeyboardLayoutInfo> {
+        self.keyboard_state
+            .as_ref()
+            .map(|keyboard_state| &keyboard_state.keyboard_layouts)
+    }
 }
 
 impl SeatHandler for WinitState {
@@ -84,8 +102,12 @@ impl SeatHandler for WinitState {
             }
             SeatCapability::Keyboard if seat_state.keyboard_state.is_none() => {
                 let keyboard = seat.get_keyboard(queue_handle, KeyboardData::new(seat.clone()));
-                seat_state.keyboard_state =
-                    Some(KeyboardState::new(keyboard, self.loop_handle.clone()));
+                seat_state.keyboard_state = Some(KeyboardState::new(
+                    keyboard,
+                    self.loop_handle.clone(),
+                    self.xkb_keymap.clone(),
+                    self.xkb_compose,
+                ));
             }
             SeatCapability::Pointer if seat_state.pointer.is_none() => {
                 let surface = self.compositor_state.create_surface(queue_handle);
@@ -208,6 +230,9 @@ impl SeatHandler for WinitState {
         seat: WlSeat,
     ) {
         let _ = self.seats.remove(&seat.id());
+        if let Some(virtual_input_manager) = self.virtual_input_manager.as_ref() {
+            virtual_input_manager.remove_seat(&seat);
+        }
     }
 }
 
@@ -62,8 +62,8 @@ impl PointerHandler for WinitState {
             };
 
             let scale_factor = window.scale_factor();
-            let position: PhysicalPosition<f64> =
-                LogicalPosition::new(event.position.0, event.position.1).to_physical(scale_factor);
+            let position: PhysicalPosition<f64> = window
+                .surface_to_physical(LogicalPosition::new(event.position.0, event.position.1));
 
             match event.kind {
                 // Pointer movements on decorations.
@@ -380,17 +380,17 @@ impl Default for WinitPointerDataInner {
     }
 }
 
+// These values are coming from <linux/input-event-codes.h>.
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+const BTN_SIDE: u32 = 0x113;
+const BTN_EXTRA: u32 = 0x114;
+const BTN_FORWARD: u32 = 0x115;
+const BTN_BACK: u32 = 0x116;
+
 /// Convert the Wayland button into winit.
 fn wayland_button_to_winit(button: u32) -> MouseButton {
-    // These values are coming from <linux/input-event-codes.h>.
-    const BTN_LEFT: u32 = 0x110;
-    const BTN_RIGHT: u32 = 0x111;
-    const BTN_MIDDLE: u32 = 0x112;
-    const BTN_SIDE: u32 = 0x113;
-    const BTN_EXTRA: u32 = 0x114;
-    const BTN_FORWARD: u32 = 0x115;
-    const BTN_BACK: u32 = 0x116;
-
     match button {
         BTN_LEFT => MouseButton::Left,
         BTN_RIGHT => MouseButton::Right,
@@ -401,6 +401,18 @@ fn wayland_button_to_winit(button: u32) -> MouseButton {
     }
 }
 
+/// The inverse of [`wayland_button_to_winit`], with the side buttons of a real mouse.
+pub fn winit_button_to_wayland(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left => BTN_LEFT,
+        MouseButton::Right => BTN_RIGHT,
+        MouseButton::Middle => BTN_MIDDLE,
+        MouseButton::Back => BTN_SIDE,
+        MouseButton::Forward => BTN_EXTRA,
+        MouseButton::Other(button) => button as u32,
+    }
+}
+
 pub trait WinitPointerDataExt {
     fn winit_data(&self) -> &WinitPointerData;
 }
@@ -503,3 +515,28 @@ delegate_dispatch!(WinitState: [ WpCursorShapeDeviceV1: GlobalData] => SeatState
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
 delegate_dispatch!(WinitState: [ZwpLockedPointerV1: GlobalData] => PointerConstraintsState);
 delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: GlobalData] => PointerConstraintsState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_round_trip() {
+        for button in [
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+            MouseButton::Back,
+            MouseButton::Forward,
+            MouseButton::Other(0x117),
+        ] {
+            assert_eq!(
+                wayland_button_to_winit(winit_button_to_wayland(button)),
+                button
+            );
+        }
+
+        assert_eq!(winit_button_to_wayland(MouseButton::Back), BTN_SIDE);
+        assert_eq!(winit_button_to_wayland(MouseButton::Forward), BTN_EXTRA);
+    }
+}
@@ -16,7 +16,7 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::
 use crate::event::{Ime, WindowEvent};
 use crate::platform_impl::wayland;
 use crate::platform_impl::wayland::state::WinitState;
-use crate::window::ImePurpose;
+use crate::window::{ImeHints, ImePurpose};
 
 pub struct TextInputState {
     text_input_manager: ZwpTextInputManagerV3,
@@ -75,7 +75,11 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
                 if window.ime_allowed() {
                     text_input.enable();
-                    text_input.set_content_type_by_purpose(window.ime_purpose());
+                    text_input
+                        .set_content_type_by_purpose(window.ime_purpose(), window.ime_hints());
+                    if let Some(surrounding_text) = window.ime_surrounding_text() {
+                        text_input.set_surrounding(surrounding_text);
+                    }
                     text_input.commit();
                     state
                         .events_sink
@@ -137,10 +141,21 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
                 // Clear preedit at the start of `Done`.
                 state.events_sink.push_window_event(
-                    WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                    WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     window_id,
                 );
 
+                // Delete the surrounding text before inserting the commit.
+                if let Some((before_bytes, after_bytes)) = text_input_data.pending_delete.take() {
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::DeleteSurrounding {
+                            before_bytes,
+                            after_bytes,
+                        }),
+                        window_id,
+                    );
+                }
+
                 // Send `Commit`.
                 if let Some(text) = text_input_data.pending_commit.take() {
                     state
@@ -155,13 +170,17 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                         .map(|b| (b, preedit.cursor_end.unwrap_or(b)));
 
                     state.events_sink.push_window_event(
-                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range)),
+                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range, Vec::new())),
                         window_id,
                     );
                 }
             }
-            TextInputEvent::DeleteSurroundingText { .. } => {
-                // Not handled.
+            TextInputEvent::DeleteSurroundingText {
+                before_length,
+                after_length,
+            } => {
+                text_input_data.pending_delete =
+                    Some((before_length as usize, after_length as usize));
             }
             _ => {}
         }
@@ -169,18 +188,126 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 }
 
 pub trait ZwpTextInputV3Ext {
-    fn set_content_type_by_purpose(&self, purpose: ImePurpose);
+    fn set_content_type_by_purpose(&self, purpose: ImePurpose, hints: ImeHints);
+
+    fn set_surrounding(&self, surrounding_text: &SurroundingText);
 }
 
 impl ZwpTextInputV3Ext for ZwpTextInputV3 {
-    fn set_content_type_by_purpose(&self, purpose: ImePurpose) {
-        let (hint, purpose) = match purpose {
-            ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
-            ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
-            ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
+    fn set_content_type_by_purpose(&self, purpose: ImePurpose, hints: ImeHints) {
+        let mut hint = [
+            (ImeHints::COMPLETION, ContentHint::Completion),
+            (ImeHints::SPELLCHECK, ContentHint::Spellcheck),
+            (
+                ImeHints::AUTO_CAPITALIZATION,
+                ContentHint::AutoCapitalization,
+            ),
+            (ImeHints::LOWERCASE, ContentHint::Lowercase),
+            (ImeHints::UPPERCASE, ContentHint::Uppercase),
+            (ImeHints::TITLECASE, ContentHint::Titlecase),
+            (ImeHints::HIDDEN_TEXT, ContentHint::HiddenText),
+            (ImeHints::SENSITIVE_DATA, ContentHint::SensitiveData),
+            (ImeHints::LATIN, ContentHint::Latin),
+            (ImeHints::MULTILINE, ContentHint::Multiline),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| hints.contains(*flag))
+        .fold(ContentHint::None, |hint, (_, content_hint)| {
+            hint | content_hint
+        });
+
+        let purpose = match purpose {
+            ImePurpose::Normal => ContentPurpose::Normal,
+            ImePurpose::Password => {
+                hint |= ContentHint::SensitiveData;
+                ContentPurpose::Password
+            }
+            ImePurpose::Terminal => ContentPurpose::Terminal,
+            ImePurpose::Alpha => ContentPurpose::Alpha,
+            ImePurpose::Digits => ContentPurpose::Digits,
+            ImePurpose::Number => ContentPurpose::Number,
+            ImePurpose::Phone => ContentPurpose::Phone,
+            ImePurpose::Url => ContentPurpose::Url,
+            ImePurpose::Email => ContentPurpose::Email,
+            ImePurpose::Name => ContentPurpose::Name,
+            ImePurpose::Pin => {
+                hint |= ContentHint::SensitiveData;
+                ContentPurpose::Pin
+            }
+            ImePurpose::Date => ContentPurpose::Date,
+            ImePurpose::Time => ContentPurpose::Time,
+            ImePurpose::DateTime => ContentPurpose::Datetime,
         };
         self.set_content_type(hint, purpose);
     }
+
+    fn set_surrounding(&self, surrounding_text: &SurroundingText) {
+        self.set_surrounding_text(
+            surrounding_text.text.clone(),
+            surrounding_text.cursor as i32,
+            surrounding_text.anchor as i32,
+        );
+    }
+}
+
+/// The maximum length of the surrounding text in bytes, as limited by the protocol.
+const MAX_SURROUNDING_TEXT_LENGTH: usize = 4000;
+
+/// The text around the cursor, sent to the input method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurroundingText {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+}
+
+impl SurroundingText {
+    /// Create the surrounding text, truncating it around the cursor when it's too long.
+    ///
+    /// When the selection doesn't fit either, it's collapsed to the cursor.
+    pub fn new(text: &str, cursor: usize, anchor: usize) -> Self {
+        let floor_char_boundary = |mut idx: usize| {
+            idx = idx.min(text.len());
+            while !text.is_char_boundary(idx) {
+                idx -= 1;
+            }
+            idx
+        };
+        let cursor = floor_char_boundary(cursor);
+        let mut anchor = floor_char_boundary(anchor);
+
+        if text.len() <= MAX_SURROUNDING_TEXT_LENGTH {
+            return Self {
+                text: text.to_owned(),
+                cursor,
+                anchor,
+            };
+        }
+
+        if cursor.abs_diff(anchor) > MAX_SURROUNDING_TEXT_LENGTH {
+            anchor = cursor;
+        }
+
+        // Center the selection in the kept text.
+        let (selection_start, selection_end) = (cursor.min(anchor), cursor.max(anchor));
+        let spare = MAX_SURROUNDING_TEXT_LENGTH - (selection_end - selection_start);
+        let start = selection_start.saturating_sub(spare / 2);
+        let end = (start + MAX_SURROUNDING_TEXT_LENGTH).min(text.len());
+        let start = end - MAX_SURROUNDING_TEXT_LENGTH;
+
+        // Don't split the characters on the edges.
+        let mut start = start;
+        while !text.is_char_boundary(start) {
+            start += 1;
+        }
+        let end = floor_char_boundary(end);
+
+        Self {
+            text: text[start..end].to_owned(),
+            cursor: cursor - start,
+            anchor: anchor - start,
+        }
+    }
 }
 
 /// The Data associated with the text input.
@@ -199,6 +326,9 @@ pub struct TextInputDataInner {
 
     /// The preedit to submit on `done`.
     pending_preedit: Option<Preedit>,
+
+    /// The lengths of the text to delete before and after the cursor on `done`.
+    pending_delete: Option<(usize, usize)>,
 }
 
 /// The state of the preedit.
@@ -210,3 +340,47 @@ struct Preedit {
 
 delegate_dispatch!(WinitState: [ZwpTextInputManagerV3: GlobalData] => TextInputState);
 delegate_dispatch!(WinitState: [ZwpTextInputV3: TextInputData] => TextInputState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrounding_text_short() {
+        let surrounding_text = SurroundingText::new("안녕하세요", 6, 3);
+        assert_eq!(surrounding_text.text, "안녕하세요");
+        assert_eq!((surrounding_text.cursor, surrounding_text.anchor), (6, 3));
+
+        // Offsets inside of a character are moved to its start.
+        let surrounding_text = SurroundingText::new("안녕", 4, 100);
+        assert_eq!((surrounding_text.cursor, surrounding_text.anchor), (3, 6));
+    }
+
+    #[test]
+    fn surrounding_text_truncated() {
+        let text = "ä".repeat(3000);
+        let surrounding_text = SurroundingText::new(&text, 3000, 3000);
+        assert_eq!(surrounding_text.text.len(), MAX_SURROUNDING_TEXT_LENGTH);
+        assert_eq!(
+            (surrounding_text.cursor, surrounding_text.anchor),
+            (2000, 2000)
+        );
+
+        // The characters on the edges aren't split.
+        let surrounding_text = SurroundingText::new(&text, 3000, 2998);
+        assert_eq!(surrounding_text.text.len(), 3998);
+        assert_eq!(
+            (surrounding_text.cursor, surrounding_text.anchor),
+            (2000, 1998)
+        );
+
+        // The cursor at the end keeps the tail of the text.
+        let surrounding_text = SurroundingText::new(&text, text.len(), 0);
+        assert_eq!(surrounding_text.text.len(), MAX_SURROUNDING_TEXT_LENGTH);
+        let offset = MAX_SURROUNDING_TEXT_LENGTH;
+        assert_eq!(
+            (surrounding_text.cursor, surrounding_text.anchor),
+            (offset, offset)
+        );
+    }
+}
@@ -26,13 +26,12 @@ impl TouchHandler for WinitState {
         position: (f64, f64),
     ) {
         let window_id = wayland::make_wid(&surface);
-        let scale_factor = match self.windows.get_mut().get(&window_id) {
-            Some(window) => window.lock().unwrap().scale_factor(),
+        let location = LogicalPosition::<f64>::from(position);
+        let physical_location = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().surface_to_physical(location),
             None => return,
         };
 
-        let location = LogicalPosition::<f64>::from(position);
-
         let seat_state = self.seats.get_mut(&touch.seat().id()).unwrap();
 
         // Update the state of the point.
@@ -46,7 +45,7 @@ impl TouchHandler for WinitState {
                     DeviceId,
                 )),
                 phase: TouchPhase::Started,
-                location: location.to_physical(scale_factor),
+                location: physical_location,
                 force: None,
                 id: id as u64,
             }),
@@ -72,8 +71,11 @@ impl TouchHandler for WinitState {
         };
 
         let window_id = wayland::make_wid(&touch_point.surface);
-        let scale_factor = match self.windows.get_mut().get(&window_id) {
-            Some(window) => window.lock().unwrap().scale_factor(),
+        let location = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window
+                .lock()
+                .unwrap()
+                .surface_to_physical(touch_point.location),
             None => return,
         };
 
@@ -83,7 +85,7 @@ impl TouchHandler for WinitState {
                     DeviceId,
                 )),
                 phase: TouchPhase::Ended,
-                location: touch_point.location.to_physical(scale_factor),
+                location,
                 force: None,
                 id: id as u64,
             }),
@@ -109,20 +111,22 @@ impl TouchHandler for WinitState {
         };
 
         let window_id = wayland::make_wid(&touch_point.surface);
-        let scale_factor = match self.windows.get_mut().get(&window_id) {
-            Some(window) => window.lock().unwrap().scale_factor(),
+        touch_point.location = LogicalPosition::<f64>::from(position);
+        let location = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window
+                .lock()
+                .unwrap()
+                .surface_to_physical(touch_point.location),
             None => return,
         };
 
-        touch_point.location = LogicalPosition::<f64>::from(position);
-
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
                     DeviceId,
                 )),
                 phase: TouchPhase::Moved,
-                location: touch_point.location.to_physical(scale_factor),
+                location,
                 force: None,
                 id: id as u64,
             }),
@@ -135,13 +139,14 @@ impl TouchHandler for WinitState {
 
         for (id, touch_point) in seat_state.touch_map.drain() {
             let window_id = wayland::make_wid(&touch_point.surface);
-            let scale_factor = match self.windows.get_mut().get(&window_id) {
-                Some(window) => window.lock().unwrap().scale_factor(),
+            let location = match self.windows.get_mut().get(&window_id) {
+                Some(window) => window
+                    .lock()
+                    .unwrap()
+                    .surface_to_physical(touch_point.location),
                 None => return,
             };
 
-            let location = touch_point.location.to_physical(scale_factor);
-
             self.events_sink.push_window_event(
                 WindowEvent::Touch(Touch {
                     device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
@@ -4,6 +4,7 @@ use std::sync::{Arc, Mutex};
 
 use ahash::AHashMap;
 
+use sctk::reexports::calloop::channel::{self, Event as ChannelEvent};
 use sctk::reexports::calloop::LoopHandle;
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::globals::GlobalList;
@@ -15,6 +16,7 @@ use sctk::compositor::{CompositorHandler, CompositorState};
 use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::SeatState;
+use sctk::shell::xdg::popup::{Popup, PopupConfigure, PopupHandler};
 use sctk::shell::xdg::window::{Window, WindowConfigure, WindowHandler};
 use sctk::shell::xdg::XdgShell;
 use sctk::shell::WaylandSurface;
@@ -22,18 +24,31 @@ use sctk::shm::slot::SlotPool;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
+use crate::event::{Event, WindowEvent};
+use crate::event_loop::SystemSettings;
+use crate::keyboard::KeyboardLayoutInfo;
+use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::platform::xkb::KeymapSource;
+use crate::platform_impl::common::portal::{self, Appearance};
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
-use crate::platform_impl::wayland::output::MonitorHandle;
+use crate::platform_impl::wayland::output::{MonitorHandle, MonitorInfo};
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, RelativePointerState, TabletState, TextInputState, WinitSeatState,
+    PointerConstraintsState, RelativePointerState, RepeatInfo, TabletState, TextInputState,
+    WinitSeatState,
 };
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::virtual_input::VirtualInputManager;
+use crate::platform_impl::wayland::types::wlr_gamma_control::GammaControlManager;
+use crate::platform_impl::wayland::types::wp_content_type::ContentTypeManager;
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
+use crate::platform_impl::wayland::types::wp_tearing_control::TearingControlManager;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
+use crate::platform_impl::wayland::types::xdg_foreign::{XdgExporter, XdgImporter};
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::XdgToplevelIconManager;
 use crate::platform_impl::wayland::window::{WindowRequests, WindowState};
 use crate::platform_impl::wayland::{WaylandError, WindowId};
-use crate::platform_impl::OsError;
+use crate::platform_impl::{MonitorHandle as PlatformMonitorHandle, OsError};
 
 /// Winit's Wayland state.
 pub struct WinitState {
@@ -85,6 +100,9 @@ pub struct WinitState {
     /// Observed monitors.
     pub monitors: Arc<Mutex<Vec<MonitorHandle>>>,
 
+    /// The last reported state of the monitors, to only notify about the actual changes.
+    pub monitor_infos: AHashMap<ObjectId, MonitorInfo>,
+
     /// Sink to accumulate window events from the compositor, which is latter dispatched in
     /// event loop run.
     pub events_sink: EventSink,
@@ -110,6 +128,39 @@ pub struct WinitState {
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// Tearing control manager.
+    pub tearing_control_manager: Option<TearingControlManager>,
+
+    /// Content type manager.
+    pub content_type_manager: Option<ContentTypeManager>,
+
+    /// Toplevel icon manager.
+    pub xdg_toplevel_icon_manager: Option<XdgToplevelIconManager>,
+
+    /// Exporter of the toplevels for the xdg-foreign.
+    pub xdg_exporter: Option<XdgExporter>,
+
+    /// Importer of the foreign toplevels for the xdg-foreign.
+    pub xdg_importer: Option<XdgImporter>,
+
+    /// Gamma control manager.
+    pub gamma_control_manager: Option<GammaControlManager>,
+
+    /// Virtual input manager, to inject the input.
+    pub virtual_input_manager: Option<VirtualInputManager>,
+
+    /// The appearance settings of the desktop.
+    pub appearance: Appearance,
+
+    /// Whether winit repeats the held keys for the new windows.
+    pub synthetic_key_repeat: bool,
+
+    /// The keymap set by the user, used instead of the keymaps of the compositor.
+    pub xkb_keymap: Option<KeymapSource>,
+
+    /// Whether winit handles the dead keys and the compose sequences.
+    pub xkb_compose: bool,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
@@ -140,10 +191,32 @@ impl WinitState {
         };
 
         let output_state = OutputState::new(globals, queue_handle);
-        let monitors = output_state.outputs().map(MonitorHandle::new).collect();
+        let gamma_control_manager = GammaControlManager::new(globals, queue_handle).ok();
+        let monitors: Vec<_> = output_state
+            .outputs()
+            .map(|output| MonitorHandle::new(output, gamma_control_manager.clone()))
+            .collect();
+        let monitor_infos = monitors
+            .iter()
+            .map(|monitor| (monitor.proxy.id(), monitor.info()))
+            .collect();
 
         let seat_state = SeatState::new(globals, queue_handle);
 
+        // The appearance is read from the portal on another thread.
+        let (appearance_sender, appearance_channel) = channel::channel();
+        let result = loop_handle.insert_source(appearance_channel, |event, _, state| {
+            if let ChannelEvent::Msg(appearance) = event {
+                state.set_appearance(appearance);
+            }
+        });
+        match result {
+            Ok(_) => portal::watch_appearance(move |appearance| {
+                appearance_sender.send(appearance).is_ok()
+            }),
+            Err(err) => log::warn!("Failed to watch the appearance settings: {}", err.error),
+        }
+
         let mut seats = AHashMap::default();
         for seat in seat_state.seats() {
             seats.insert(seat.id(), WinitSeatState::new());
@@ -185,6 +258,18 @@ impl WinitState {
             viewporter_state,
             fractional_scaling_manager,
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            tearing_control_manager: TearingControlManager::new(globals, queue_handle).ok(),
+            content_type_manager: ContentTypeManager::new(globals, queue_handle).ok(),
+            xdg_toplevel_icon_manager: XdgToplevelIconManager::new(globals, queue_handle).ok(),
+            xdg_exporter: XdgExporter::new(globals, queue_handle).ok(),
+            xdg_importer: XdgImporter::new(globals, queue_handle).ok(),
+            gamma_control_manager,
+            virtual_input_manager: VirtualInputManager::new(globals, queue_handle).ok(),
+            // The appearance is set once it's read from the portal.
+            appearance: Appearance::default(),
+            synthetic_key_repeat: true,
+            xkb_keymap: None,
+            xkb_compose: true,
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
@@ -197,6 +282,7 @@ impl WinitState {
             pointer_surfaces: Default::default(),
 
             monitors: Arc::new(Mutex::new(monitors)),
+            monitor_infos,
             events_sink: EventSink::new(),
             loop_handle,
             // Make it true by default.
@@ -204,6 +290,67 @@ impl WinitState {
         })
     }
 
+    /// Set the appearance settings of the desktop, notifying the windows about the changes.
+    pub fn set_appearance(&mut self, appearance: Appearance) {
+        let prev_appearance = std::mem::replace(&mut self.appearance, appearance);
+        if appearance == prev_appearance {
+            return;
+        }
+
+        let accent_color = appearance
+            .accent_color
+            .filter(|&accent_color| Some(accent_color) != prev_appearance.accent_color);
+        for (window_id, window_state) in self.windows.get_mut() {
+            let mut window_state = window_state.lock().unwrap();
+            if window_state.set_appearance(appearance) {
+                if let Some(theme) = window_state.theme() {
+                    self.events_sink
+                        .push_window_event(WindowEvent::ThemeChanged(theme), *window_id);
+                }
+            }
+
+            if let Some(accent_color) = accent_color {
+                self.events_sink
+                    .push_window_event(WindowEvent::AccentColorChanged(accent_color), *window_id);
+            }
+        }
+
+        self.dispatched_events = true;
+    }
+
+    /// The key repeat of the first keyboard.
+    pub fn repeat_info(&self) -> Option<crate::keyboard::RepeatInfo> {
+        let repeat_info = self.seats.values().find_map(WinitSeatState::repeat_info)?;
+        Some(match repeat_info {
+            RepeatInfo::Repeat { gap, delay } => crate::keyboard::RepeatInfo::Repeat {
+                delay,
+                interval: gap,
+            },
+            RepeatInfo::Disable => crate::keyboard::RepeatInfo::Disabled,
+        })
+    }
+
+    /// The keyboard layouts of the first keyboard.
+    pub fn keyboard_layouts(&self) -> KeyboardLayoutInfo {
+        self.seats
+            .values()
+            .find_map(WinitSeatState::keyboard_layouts)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The system settings, where only the key repeat is known.
+    pub fn system_settings(&self) -> SystemSettings {
+        let mut settings = SystemSettings::default();
+        let repeat_info = self.seats.values().find_map(WinitSeatState::repeat_info);
+        if let Some(RepeatInfo::Repeat { gap, delay }) = repeat_info {
+            settings.key_repeat_delay = Some(delay);
+            settings.key_repeat_interval = Some(gap);
+        }
+
+        settings
+    }
+
     pub fn scale_factor_changed(
         &mut self,
         surface: &WlSurface,
@@ -314,34 +461,107 @@ impl WindowHandler for WinitState {
     }
 }
 
+impl PopupHandler for WinitState {
+    fn configure(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        popup: &Popup,
+        configure: PopupConfigure,
+    ) {
+        let window_id = super::make_wid(popup.wl_surface());
+
+        let pos = if let Some(pos) = self
+            .window_compositor_updates
+            .iter()
+            .position(|update| update.window_id == window_id)
+        {
+            pos
+        } else {
+            self.window_compositor_updates
+                .push(WindowCompositorUpdate::new(window_id));
+            self.window_compositor_updates.len() - 1
+        };
+
+        // Populate the configure to the popup.
+        self.window_compositor_updates[pos].resized |= self
+            .windows
+            .get_mut()
+            .get_mut(&window_id)
+            .expect("got configure for dead popup.")
+            .lock()
+            .unwrap()
+            .configure_popup(configure);
+    }
+
+    fn done(&mut self, _: &Connection, _: &QueueHandle<Self>, popup: &Popup) {
+        let window_id = super::make_wid(popup.wl_surface());
+        self.events_sink
+            .push_window_event(WindowEvent::PopupDone, window_id);
+    }
+}
+
 impl OutputHandler for WinitState {
     fn output_state(&mut self) -> &mut OutputState {
         &mut self.output_state
     }
 
     fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: WlOutput) {
-        self.monitors
-            .lock()
-            .unwrap()
-            .push(MonitorHandle::new(output));
+        let mut monitors = self.monitors.lock().unwrap();
+        let added = MonitorHandle::new(output, self.gamma_control_manager.clone());
+
+        // The outputs present on startup are already known.
+        if monitors.contains(&added) {
+            return;
+        }
+
+        monitors.push(added.clone());
+        self.monitor_infos.insert(added.proxy.id(), added.info());
+        self.events_sink
+            .push_monitor_event(Event::MonitorAdded(RootMonitorHandle {
+                inner: PlatformMonitorHandle::Wayland(added),
+            }));
     }
 
     fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, updated: WlOutput) {
         let mut monitors = self.monitors.lock().unwrap();
-        let updated = MonitorHandle::new(updated);
+        let updated = MonitorHandle::new(updated, self.gamma_control_manager.clone());
+
+        // The output could be updated without any change visible to the clients.
+        let info = updated.info();
+        if self.monitor_infos.get(&updated.proxy.id()) == Some(&info) {
+            return;
+        }
+        self.monitor_infos.insert(updated.proxy.id(), info);
+
         if let Some(pos) = monitors.iter().position(|output| output == &updated) {
-            monitors[pos] = updated
+            monitors[pos] = updated.clone()
         } else {
-            monitors.push(updated)
+            monitors.push(updated.clone())
         }
+
+        self.events_sink
+            .push_monitor_event(Event::MonitorChanged(RootMonitorHandle {
+                inner: PlatformMonitorHandle::Wayland(updated),
+            }));
     }
 
     fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, removed: WlOutput) {
         let mut monitors = self.monitors.lock().unwrap();
-        let removed = MonitorHandle::new(removed);
+        if let Some(gamma_control_manager) = self.gamma_control_manager.as_ref() {
+            gamma_control_manager.remove_output(&removed);
+        }
+
+        self.monitor_infos.remove(&removed.id());
+        let removed = MonitorHandle::new(removed, self.gamma_control_manager.clone());
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
             monitors.remove(pos);
         }
+
+        self.events_sink
+            .push_monitor_event(Event::MonitorRemoved(RootMonitorHandle {
+                inner: PlatformMonitorHandle::Wayland(removed),
+            }));
     }
 }
 
@@ -366,26 +586,28 @@ impl CompositorHandler for WinitState {
         self.scale_factor_changed(surface, scale_factor as f64, true)
     }
 
-    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, surface: &WlSurface, _: u32) {
+    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, surface: &WlSurface, time: u32) {
         let window_id = super::make_wid(surface);
         let window = match self.windows.get_mut().get(&window_id) {
             Some(window) => window,
             None => return,
         };
 
+        let window_requests = self.window_requests.get_mut().get(&window_id).unwrap();
+
+        // Turn the explicitly requested frame into the redraw request.
+        if window_requests.take_frame_requested() {
+            window_requests
+                .redraw_requested
+                .store(true, Ordering::Relaxed);
+        }
+
         // In case we have a redraw requested we must indicate the wake up.
-        if self
-            .window_requests
-            .get_mut()
-            .get(&window_id)
-            .unwrap()
-            .redraw_requested
-            .load(Ordering::Relaxed)
-        {
+        if window_requests.redraw_requested.load(Ordering::Relaxed) {
             self.dispatched_events = true;
         }
 
-        window.lock().unwrap().frame_callback_received();
+        window.lock().unwrap().frame_callback_received(time);
     }
 }
 
@@ -431,3 +653,4 @@ sctk::delegate_registry!(WinitState);
 sctk::delegate_shm!(WinitState);
 sctk::delegate_xdg_shell!(WinitState);
 sctk::delegate_xdg_window!(WinitState);
+sctk::delegate_xdg_popup!(WinitState);
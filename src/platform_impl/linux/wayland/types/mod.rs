@@ -2,6 +2,12 @@
 
 pub mod cursor;
 pub mod kwin_blur;
+pub mod virtual_input;
+pub mod wlr_gamma_control;
+pub mod wp_content_type;
 pub mod wp_fractional_scaling;
+pub mod wp_tearing_control;
 pub mod wp_viewporter;
 pub mod xdg_activation;
+pub mod xdg_foreign;
+pub mod xdg_toplevel_icon;
@@ -0,0 +1,289 @@
+//! Handling of the virtual-keyboard and of the wlr-virtual-pointer, to inject the input.
+
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::os::unix::io::{AsFd, FromRawFd, OwnedFd};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_keyboard::{KeyState, KeymapFormat};
+use sctk::reexports::client::protocol::wl_pointer::ButtonState;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use wayland_protocols_wlr::virtual_pointer::v1::client::{
+    zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1,
+    zwlr_virtual_pointer_v1::ZwlrVirtualPointerV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::error::{ExternalError, NotSupportedError};
+use crate::event::ElementState;
+use crate::platform_impl::wayland::protocols::virtual_keyboard::{
+    zwp_virtual_keyboard_manager_v1::ZwpVirtualKeyboardManagerV1,
+    zwp_virtual_keyboard_v1::ZwpVirtualKeyboardV1,
+};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::OsError;
+
+/// Virtual input manager.
+///
+/// The virtual devices are created on the first injected input, for the seat of the latest
+/// keyboard keymap.
+#[derive(Debug, Clone)]
+pub struct VirtualInputManager {
+    keyboard_manager: Option<ZwpVirtualKeyboardManagerV1>,
+    pointer_manager: Option<ZwlrVirtualPointerManagerV1>,
+    queue_handle: QueueHandle<WinitState>,
+    state: Arc<Mutex<VirtualInputState>>,
+}
+
+#[derive(Debug)]
+struct VirtualInputState {
+    /// The seat of the virtual devices.
+    seat: Option<WlSeat>,
+
+    /// The keymap of the seat, also used by the virtual keyboard.
+    keymap: Option<String>,
+
+    keyboard: Option<ZwpVirtualKeyboardV1>,
+
+    pointer: Option<ZwlrVirtualPointerV1>,
+
+    /// The origin of the timestamps of the injected input.
+    start: Instant,
+}
+
+impl VirtualInputState {
+    fn destroy_devices(&mut self) {
+        if let Some(keyboard) = self.keyboard.take() {
+            keyboard.destroy();
+        }
+        if let Some(pointer) = self.pointer.take() {
+            pointer.destroy();
+        }
+    }
+
+    fn time(&self) -> u32 {
+        self.start.elapsed().as_millis() as u32
+    }
+}
+
+impl VirtualInputManager {
+    /// Create new virtual input manager.
+    ///
+    /// Fails when neither the virtual keyboard nor the virtual pointer are available.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let keyboard_manager: Option<ZwpVirtualKeyboardManagerV1> =
+            globals.bind(queue_handle, 1..=1, GlobalData).ok();
+        let pointer_manager: Option<ZwlrVirtualPointerManagerV1> =
+            globals.bind(queue_handle, 1..=1, GlobalData).ok();
+        if keyboard_manager.is_none() && pointer_manager.is_none() {
+            return Err(BindError::NotPresent);
+        }
+
+        Ok(Self {
+            keyboard_manager,
+            pointer_manager,
+            queue_handle: queue_handle.clone(),
+            state: Arc::new(Mutex::new(VirtualInputState {
+                seat: None,
+                keymap: None,
+                keyboard: None,
+                pointer: None,
+                start: Instant::now(),
+            })),
+        })
+    }
+
+    /// Update the keymap of the seat, used for the injected keys.
+    pub fn set_keymap(&self, seat: &WlSeat, keymap: String) {
+        let mut state = self.state.lock().unwrap();
+        if state.seat.as_ref() != Some(seat) {
+            state.destroy_devices();
+            state.seat = Some(seat.clone());
+        }
+
+        if state.keymap.as_ref() != Some(&keymap) {
+            if let Some(keyboard) = state.keyboard.as_ref() {
+                // A keyboard without a keymap can't send keys, create it again on the next key.
+                if let Err(err) = upload_keymap(keyboard, &keymap) {
+                    log::warn!("Failed to upload the keymap of the virtual keyboard: {err}");
+                    if let Some(keyboard) = state.keyboard.take() {
+                        keyboard.destroy();
+                    }
+                }
+            }
+            state.keymap = Some(keymap);
+        }
+    }
+
+    /// Destroy the virtual devices of the removed seat.
+    pub fn remove_seat(&self, seat: &WlSeat) {
+        let mut state = self.state.lock().unwrap();
+        if state.seat.as_ref() == Some(seat) {
+            state.destroy_devices();
+            state.seat = None;
+            state.keymap = None;
+        }
+    }
+
+    /// Inject the key with the given evdev scancode.
+    pub fn inject_key(&self, scancode: u32, key_state: ElementState) -> Result<(), ExternalError> {
+        let manager = match self.keyboard_manager.as_ref() {
+            Some(manager) => manager,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let time = state.time();
+        let state = &mut *state;
+        let (seat, keymap) = match (state.seat.as_ref(), state.keymap.as_ref()) {
+            (Some(seat), Some(keymap)) => (seat, keymap),
+            _ => return Err(no_keyboard()),
+        };
+
+        let keyboard = match state.keyboard.as_ref() {
+            Some(keyboard) => keyboard,
+            None => {
+                let keyboard =
+                    manager.create_virtual_keyboard(seat, &self.queue_handle, GlobalData);
+                if let Err(err) = upload_keymap(&keyboard, keymap) {
+                    log::warn!("Failed to upload the keymap of the virtual keyboard: {err}");
+                    keyboard.destroy();
+                    return Err(ExternalError::Os(os_error!(OsError::Misc(
+                        "failed to upload the keymap of the virtual keyboard."
+                    ))));
+                }
+                state.keyboard.insert(keyboard)
+            }
+        };
+
+        let key_state = match key_state {
+            ElementState::Pressed => KeyState::Pressed,
+            ElementState::Released => KeyState::Released,
+        };
+        keyboard.key(time, scancode, key_state.into());
+
+        Ok(())
+    }
+
+    /// Inject the button with the given evdev code.
+    pub fn inject_button(
+        &self,
+        button: u32,
+        button_state: ElementState,
+    ) -> Result<(), ExternalError> {
+        let manager = match self.pointer_manager.as_ref() {
+            Some(manager) => manager,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let time = state.time();
+        let seat = state.seat.clone();
+        let pointer = state.pointer.get_or_insert_with(|| {
+            manager.create_virtual_pointer(seat.as_ref(), &self.queue_handle, GlobalData)
+        });
+
+        let button_state = match button_state {
+            ElementState::Pressed => ButtonState::Pressed,
+            ElementState::Released => ButtonState::Released,
+        };
+        pointer.button(time, button, button_state);
+        pointer.frame();
+
+        Ok(())
+    }
+}
+
+fn no_keyboard() -> ExternalError {
+    ExternalError::Os(os_error!(OsError::Misc(
+        "no keyboard keymap was received from the compositor."
+    )))
+}
+
+/// Write the keymap to a memory backed file and send it to the compositor.
+fn upload_keymap(keyboard: &ZwpVirtualKeyboardV1, keymap: &str) -> io::Result<()> {
+    let fd =
+        unsafe { libc::memfd_create(b"winit-virtual-keymap\0".as_ptr().cast(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // The keymap is sent as a NUL terminated string, like the keymaps of the compositors.
+    let mut file = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+    file.write_all(keymap.as_bytes())?;
+    file.write_all(&[0])?;
+    file.seek(SeekFrom::Start(0))?;
+
+    keyboard.keymap(
+        KeymapFormat::XkbV1.into(),
+        file.as_fd(),
+        keymap.len() as u32 + 1,
+    );
+
+    Ok(())
+}
+
+impl Dispatch<ZwpVirtualKeyboardManagerV1, GlobalData, WinitState> for VirtualInputManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpVirtualKeyboardManagerV1,
+        _: <ZwpVirtualKeyboardManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwpVirtualKeyboardV1, GlobalData, WinitState> for VirtualInputManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpVirtualKeyboardV1,
+        _: <ZwpVirtualKeyboardV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrVirtualPointerManagerV1, GlobalData, WinitState> for VirtualInputManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrVirtualPointerManagerV1,
+        _: <ZwlrVirtualPointerManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrVirtualPointerV1, GlobalData, WinitState> for VirtualInputManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrVirtualPointerV1,
+        _: <ZwlrVirtualPointerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpVirtualKeyboardManagerV1: GlobalData] => VirtualInputManager);
+delegate_dispatch!(WinitState: [ZwpVirtualKeyboardV1: GlobalData] => VirtualInputManager);
+delegate_dispatch!(WinitState: [ZwlrVirtualPointerManagerV1: GlobalData] => VirtualInputManager);
+delegate_dispatch!(WinitState: [ZwlrVirtualPointerV1: GlobalData] => VirtualInputManager);
@@ -0,0 +1,258 @@
+//! Handling of the wlr-gamma-control.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::os::unix::io::{AsFd, FromRawFd, OwnedFd};
+use std::sync::{Arc, Mutex};
+
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use wayland_protocols_wlr::gamma_control::v1::client::{
+    zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1,
+    zwlr_gamma_control_v1::{Event as GammaControlEvent, ZwlrGammaControlV1},
+};
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+
+use crate::error::ExternalError;
+use crate::monitor::GammaRamp;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::OsError;
+
+/// The size of the ramp reported before the compositor sent the real one.
+const DEFAULT_GAMMA_SIZE: usize = 256;
+
+/// Gamma control manager.
+///
+/// The gamma controls are shared by all the monitor handles, since the compositor allows only
+/// one control per output.
+#[derive(Debug, Clone)]
+pub struct GammaControlManager {
+    manager: ZwlrGammaControlManagerV1,
+    queue_handle: QueueHandle<WinitState>,
+    controls: Arc<Mutex<GammaControls>>,
+}
+
+#[derive(Debug, Default)]
+struct GammaControls {
+    /// The gamma controls of the outputs, with the surface of the fullscreen window they're
+    /// restored for.
+    controls: HashMap<ObjectId, (ZwlrGammaControlV1, Option<ObjectId>)>,
+
+    /// The surfaces of the fullscreen windows.
+    fullscreen_surfaces: Vec<WlSurface>,
+}
+
+impl GammaControlManager {
+    /// Create new gamma control manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            queue_handle: queue_handle.clone(),
+            controls: Default::default(),
+        })
+    }
+
+    /// Get the gamma ramp last set on the output, or a linear one.
+    pub fn gamma_ramp(&self, output: &WlOutput) -> Result<GammaRamp, ExternalError> {
+        let controls = self.controls.lock().unwrap();
+        let state = match controls.controls.get(&output.id()) {
+            Some((control, _)) => control.data::<GammaControlData>().unwrap().lock(),
+            None => return Ok(GammaRamp::from_gamma(DEFAULT_GAMMA_SIZE, 1.)),
+        };
+
+        if state.failed {
+            return Err(control_failed());
+        }
+
+        Ok(state
+            .ramp
+            .clone()
+            .unwrap_or_else(|| GammaRamp::from_gamma(state.size.unwrap_or(DEFAULT_GAMMA_SIZE), 1.)))
+    }
+
+    /// Set the gamma ramp on the output.
+    ///
+    /// The ramp is applied once the compositor sends the size of the gamma ramps, and is owned
+    /// by the fullscreen window on the output.
+    pub fn set_gamma_ramp(&self, output: &WlOutput, ramp: &GammaRamp) -> Result<(), ExternalError> {
+        let mut controls = self.controls.lock().unwrap();
+        let owner = controls
+            .fullscreen_surfaces
+            .iter()
+            .find(|surface| {
+                surface.data::<SurfaceData>().is_some_and(|data| {
+                    data.outputs()
+                        .any(|surface_output| &surface_output == output)
+                })
+            })
+            .map(Proxy::id);
+
+        let (control, control_owner) = controls.controls.entry(output.id()).or_insert_with(|| {
+            let control =
+                self.manager
+                    .get_gamma_control(output, &self.queue_handle, Default::default());
+            (control, None)
+        });
+        *control_owner = owner;
+
+        let mut state = control.data::<GammaControlData>().unwrap().lock();
+        if state.failed {
+            return Err(control_failed());
+        }
+
+        state.ramp = Some(ramp.clone());
+        if let Some(size) = state.size {
+            apply_gamma_ramp(control, &ramp.resample(size));
+        }
+
+        Ok(())
+    }
+
+    /// Destroy the gamma control of the removed output.
+    pub fn remove_output(&self, output: &WlOutput) {
+        if let Some((control, _)) = self.controls.lock().unwrap().controls.remove(&output.id()) {
+            control.destroy();
+        }
+    }
+
+    /// The window with the surface entered the fullscreen, so it owns the ramps set on its
+    /// outputs.
+    pub fn add_fullscreen_surface(&self, surface: &WlSurface) {
+        let mut controls = self.controls.lock().unwrap();
+        if !controls.fullscreen_surfaces.contains(surface) {
+            controls.fullscreen_surfaces.push(surface.clone());
+        }
+    }
+
+    /// The window with the surface left the fullscreen or was closed, destroy the gamma controls
+    /// it owns so the compositor restores the original gamma ramps of their outputs.
+    pub fn remove_fullscreen_surface(&self, surface: &WlSurface) {
+        let mut controls = self.controls.lock().unwrap();
+        controls
+            .fullscreen_surfaces
+            .retain(|fullscreen_surface| fullscreen_surface != surface);
+
+        let surface_id = surface.id();
+        controls.controls.retain(|_, (control, owner)| {
+            if owner.as_ref() != Some(&surface_id) {
+                return true;
+            }
+
+            control.destroy();
+            false
+        });
+    }
+}
+
+/// The state of the gamma control of an output.
+#[derive(Debug, Default)]
+pub struct GammaControlData {
+    state: Mutex<GammaControlState>,
+}
+
+impl GammaControlData {
+    fn lock(&self) -> std::sync::MutexGuard<'_, GammaControlState> {
+        self.state.lock().unwrap()
+    }
+}
+
+#[derive(Debug, Default)]
+struct GammaControlState {
+    /// The size of the ramps, sent by the compositor.
+    size: Option<usize>,
+
+    /// The ramp requested by the user.
+    ramp: Option<GammaRamp>,
+
+    /// Whether the control is no longer valid.
+    failed: bool,
+}
+
+fn control_failed() -> ExternalError {
+    ExternalError::Os(os_error!(OsError::Misc(
+        "the gamma of the output is controlled by another client."
+    )))
+}
+
+/// Write the ramps to a memory backed file and send it to the compositor.
+fn apply_gamma_ramp(control: &ZwlrGammaControlV1, ramp: &GammaRamp) {
+    let fd =
+        unsafe { libc::memfd_create(b"winit-gamma-ramp\0".as_ptr().cast(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        log::warn!(
+            "Failed to create the gamma ramp file: {}",
+            std::io::Error::last_os_error()
+        );
+        return;
+    }
+
+    let mut file = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+    let bytes: Vec<u8> = [&ramp.red, &ramp.green, &ramp.blue]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.to_ne_bytes())
+        .collect();
+    if let Err(err) = file
+        .write_all(&bytes)
+        .and_then(|_| file.seek(SeekFrom::Start(0)))
+    {
+        log::warn!("Failed to write the gamma ramp: {err}");
+        return;
+    }
+
+    control.set_gamma(file.as_fd());
+}
+
+impl Dispatch<ZwlrGammaControlManagerV1, GlobalData, WinitState> for GammaControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrGammaControlManagerV1,
+        _: <ZwlrGammaControlManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrGammaControlV1, GammaControlData, WinitState> for GammaControlManager {
+    fn event(
+        _: &mut WinitState,
+        control: &ZwlrGammaControlV1,
+        event: <ZwlrGammaControlV1 as Proxy>::Event,
+        data: &GammaControlData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let mut state = data.lock();
+        match event {
+            GammaControlEvent::GammaSize { size } => {
+                let size = size as usize;
+                state.size = Some(size);
+                if let Some(ramp) = state.ramp.as_ref() {
+                    apply_gamma_ramp(control, &ramp.resample(size));
+                }
+            }
+            GammaControlEvent::Failed => {
+                log::warn!("Failed to control the gamma of the output");
+                state.failed = true;
+            }
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrGammaControlManagerV1: GlobalData] => GammaControlManager);
+delegate_dispatch!(WinitState: [ZwlrGammaControlV1: GammaControlData] => GammaControlManager);
@@ -0,0 +1,67 @@
+//! Handling of the wp-tearing-control.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_manager_v1::WpTearingControlManagerV1;
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::WpTearingControlV1;
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Tearing control manager.
+#[derive(Debug, Clone)]
+pub struct TearingControlManager {
+    manager: WpTearingControlManagerV1,
+}
+
+impl TearingControlManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Get the tearing control for the given surface.
+    pub fn tearing_control(
+        &self,
+        surface: &WlSurface,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> WpTearingControlV1 {
+        self.manager
+            .get_tearing_control(surface, queue_handle, GlobalData)
+    }
+}
+
+impl Dispatch<WpTearingControlManagerV1, GlobalData, WinitState> for TearingControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpTearingControlManagerV1,
+        _: <WpTearingControlManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WpTearingControlV1, GlobalData, WinitState> for TearingControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpTearingControlV1,
+        _: <WpTearingControlV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+delegate_dispatch!(WinitState: [WpTearingControlManagerV1: GlobalData] => TearingControlManager);
+delegate_dispatch!(WinitState: [WpTearingControlV1: GlobalData] => TearingControlManager);
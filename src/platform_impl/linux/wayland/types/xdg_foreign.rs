@@ -0,0 +1,161 @@
+//! Handling of the xdg-foreign.
+
+use log::warn;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_exported_v2::{
+    Event as ExportedEvent, ZxdgExportedV2,
+};
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_exporter_v2::ZxdgExporterV2;
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_imported_v2::{
+    Event as ImportedEvent, ZxdgImportedV2,
+};
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_importer_v2::ZxdgImporterV2;
+
+use sctk::globals::GlobalData;
+
+use crate::event_loop::AsyncRequestSerial;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::WindowId;
+
+/// Exporter of the toplevels.
+#[derive(Debug, Clone)]
+pub struct XdgExporter {
+    exporter: ZxdgExporterV2,
+}
+
+impl XdgExporter {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let exporter = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { exporter })
+    }
+
+    /// Export the toplevel surface, the handle is delivered to the window with the given data.
+    pub fn export_toplevel(
+        &self,
+        surface: &WlSurface,
+        queue_handle: &QueueHandle<WinitState>,
+        data: XdgExportedData,
+    ) -> ZxdgExportedV2 {
+        self.exporter.export_toplevel(surface, queue_handle, data)
+    }
+}
+
+/// Importer of the foreign toplevels.
+#[derive(Debug, Clone)]
+pub struct XdgImporter {
+    importer: ZxdgImporterV2,
+}
+
+impl XdgImporter {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let importer = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { importer })
+    }
+
+    /// Import the toplevel exported with the given handle.
+    pub fn import_toplevel(
+        &self,
+        handle: String,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ZxdgImportedV2 {
+        self.importer
+            .import_toplevel(handle, queue_handle, GlobalData)
+    }
+}
+
+/// The data associated with the export request.
+#[derive(Debug)]
+pub struct XdgExportedData {
+    /// The window being exported.
+    pub window_id: WindowId,
+
+    /// The serial of the request.
+    pub serial: AsyncRequestSerial,
+}
+
+impl Dispatch<ZxdgExporterV2, GlobalData, WinitState> for XdgExporter {
+    fn event(
+        _: &mut WinitState,
+        _: &ZxdgExporterV2,
+        _: <ZxdgExporterV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZxdgExportedV2, XdgExportedData, WinitState> for XdgExporter {
+    fn event(
+        state: &mut WinitState,
+        _: &ZxdgExportedV2,
+        event: <ZxdgExportedV2 as Proxy>::Event,
+        data: &XdgExportedData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let handle = match event {
+            ExportedEvent::Handle { handle } => handle,
+            _ => return,
+        };
+
+        state.events_sink.push_window_event(
+            crate::event::WindowEvent::ExportHandleDone {
+                serial: data.serial,
+                handle,
+            },
+            data.window_id,
+        );
+    }
+}
+
+impl Dispatch<ZxdgImporterV2, GlobalData, WinitState> for XdgImporter {
+    fn event(
+        _: &mut WinitState,
+        _: &ZxdgImporterV2,
+        _: <ZxdgImporterV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZxdgImportedV2, GlobalData, WinitState> for XdgImporter {
+    fn event(
+        state: &mut WinitState,
+        proxy: &ZxdgImportedV2,
+        event: <ZxdgImportedV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        if let ImportedEvent::Destroyed = event {
+            warn!("The foreign parent of the window is no longer valid");
+
+            // The window owns the imported object, so it must be the one to destroy it.
+            for window in state.windows.get_mut().values() {
+                if window.lock().unwrap().foreign_parent_destroyed(proxy) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ZxdgExporterV2: GlobalData] => XdgExporter);
+delegate_dispatch!(WinitState: [ZxdgExportedV2: XdgExportedData] => XdgExporter);
+delegate_dispatch!(WinitState: [ZxdgImporterV2: GlobalData] => XdgImporter);
+delegate_dispatch!(WinitState: [ZxdgImportedV2: GlobalData] => XdgImporter);
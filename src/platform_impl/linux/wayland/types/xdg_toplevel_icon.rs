@@ -0,0 +1,160 @@
+//! Handling of the xdg-toplevel-icon.
+
+use std::sync::{Arc, Mutex};
+
+use log::warn;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_shm::Format;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
+use sctk::shm::slot::{Buffer, SlotPool};
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::protocols::xdg_toplevel_icon::xdg_toplevel_icon_manager_v1::{
+    Event as IconManagerEvent, XdgToplevelIconManagerV1,
+};
+use crate::platform_impl::wayland::protocols::xdg_toplevel_icon::xdg_toplevel_icon_v1::XdgToplevelIconV1;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::PlatformIcon;
+
+/// Toplevel icon manager.
+#[derive(Debug, Clone)]
+pub struct XdgToplevelIconManager {
+    manager: XdgToplevelIconManagerV1,
+
+    /// The icon sizes preferred by the compositor.
+    sizes: Arc<Mutex<Vec<i32>>>,
+
+    /// The icon sizes received before the `done` event.
+    pending_sizes: Vec<i32>,
+}
+
+impl XdgToplevelIconManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            sizes: Default::default(),
+            pending_sizes: Vec::new(),
+        })
+    }
+
+    /// Create a new, empty, icon.
+    pub fn create_icon(&self, queue_handle: &QueueHandle<WinitState>) -> XdgToplevelIconV1 {
+        self.manager.create_icon(queue_handle, GlobalData)
+    }
+
+    /// Set the icon of the given toplevel, `None` resets it to the default one.
+    pub fn set_icon(&self, toplevel: &XdgToplevel, icon: Option<&XdgToplevelIconV1>) {
+        self.manager.set_icon(toplevel, icon);
+    }
+
+    /// The sizes to upload the given icon at.
+    ///
+    /// When the compositor doesn't have any preference, the icon is used at its native size.
+    pub(crate) fn icon_sizes(&self, icon: &PlatformIcon) -> Vec<i32> {
+        let sizes = self.sizes.lock().unwrap();
+        if sizes.is_empty() {
+            vec![icon.width.max(icon.height) as i32]
+        } else {
+            sizes.clone()
+        }
+    }
+}
+
+/// Create the `size`x`size` buffer for the icon.
+///
+/// The icon is scaled with the nearest neighbour filter, keeping its aspect ratio, and centered.
+pub(crate) fn create_icon_buffer(
+    pool: &mut SlotPool,
+    icon: &PlatformIcon,
+    size: i32,
+) -> Option<Buffer> {
+    if icon.width == 0 || icon.height == 0 || size <= 0 {
+        return None;
+    }
+
+    let (buffer, canvas) = match pool.create_buffer(size, size, 4 * size, Format::Argb8888) {
+        Ok(buffer) => buffer,
+        Err(err) => {
+            warn!("Failed to create the icon buffer: {err}");
+            return None;
+        }
+    };
+    canvas.fill(0);
+
+    let size = size as usize;
+    let (width, height) = (icon.width as usize, icon.height as usize);
+    let scale = f64::min(size as f64 / width as f64, size as f64 / height as f64);
+    let dst_width = ((width as f64 * scale).round() as usize).clamp(1, size);
+    let dst_height = ((height as f64 * scale).round() as usize).clamp(1, size);
+    let (offset_x, offset_y) = ((size - dst_width) / 2, (size - dst_height) / 2);
+
+    for y in 0..dst_height {
+        let src_y = y * height / dst_height;
+        for x in 0..dst_width {
+            let src_x = x * width / dst_width;
+            let src = &icon.rgba[4 * (src_y * width + src_x)..][..4];
+            let dst = &mut canvas[4 * ((offset_y + y) * size + offset_x + x)..][..4];
+
+            // The `wl_shm` formats use premultiplied alpha.
+            let alpha = src[3] as u32;
+            dst[0] = (src[2] as u32 * alpha / 255) as u8;
+            dst[1] = (src[1] as u32 * alpha / 255) as u8;
+            dst[2] = (src[0] as u32 * alpha / 255) as u8;
+            dst[3] = src[3];
+        }
+    }
+
+    Some(buffer)
+}
+
+impl Dispatch<XdgToplevelIconManagerV1, GlobalData, WinitState> for XdgToplevelIconManager {
+    fn event(
+        state: &mut WinitState,
+        _: &XdgToplevelIconManagerV1,
+        event: <XdgToplevelIconManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let manager = match state.xdg_toplevel_icon_manager.as_mut() {
+            Some(manager) => manager,
+            None => return,
+        };
+
+        match event {
+            IconManagerEvent::IconSize { size } => manager.pending_sizes.push(size),
+            IconManagerEvent::Done => {
+                *manager.sizes.lock().unwrap() = std::mem::take(&mut manager.pending_sizes);
+
+                // Upload the icons of the existing windows at the new sizes.
+                for window in state.windows.get_mut().values() {
+                    window.lock().unwrap().reload_icon();
+                }
+            }
+        }
+    }
+}
+
+impl Dispatch<XdgToplevelIconV1, GlobalData, WinitState> for XdgToplevelIconManager {
+    fn event(
+        _: &mut WinitState,
+        _: &XdgToplevelIconV1,
+        _: <XdgToplevelIconV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+delegate_dispatch!(WinitState: [XdgToplevelIconManagerV1: GlobalData] => XdgToplevelIconManager);
+delegate_dispatch!(WinitState: [XdgToplevelIconV1: GlobalData] => XdgToplevelIconManager);
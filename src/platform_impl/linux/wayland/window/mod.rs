@@ -11,38 +11,46 @@ use sctk::reexports::client::QueueHandle;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
 use sctk::reexports::protocols::xdg::activation::v1::client::xdg_activation_v1::XdgActivationV1;
+use sctk::shell::xdg::popup::Popup;
 use sctk::shell::xdg::window::Window as SctkWindow;
 use sctk::shell::xdg::window::WindowDecorations;
+use sctk::shell::xdg::{XdgPositioner, XdgSurface};
 use sctk::shell::WaylandSurface;
 
 use log::warn;
 
-use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event::{Ime, WindowEvent};
+use crate::event::{ElementState, Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::keyboard::PhysicalKey;
 use crate::platform_impl::{
-    Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
+    physicalkey_to_scancode, Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError,
+    PlatformIcon, VideoModeHandle as PlatformVideoModeHandle,
 };
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    AccentColor, ContentType, Cursor, CursorGrabMode, ImeHints, ImePurpose, InjectedPointer,
+    PopupAttributes, PresentationHint, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
+    WindowButtons, WindowLevel,
 };
 
 use super::event_loop::sink::EventSink;
 use super::output::MonitorHandle;
+use super::seat::{winit_button_to_wayland, SurroundingText};
 use super::state::WinitState;
+use super::types::virtual_input::VirtualInputManager;
 use super::types::xdg_activation::XdgActivationTokenData;
 use super::{EventLoopWindowTarget, WaylandError, WindowId};
 
 pub(crate) mod state;
 
 pub use state::WindowState;
+use state::{FrameCallbackState, ShellSurface};
 
 /// The Wayland window.
 pub struct Window {
-    /// Reference to the underlying SCTK window.
-    window: SctkWindow,
+    /// Reference to the underlying SCTK shell surface.
+    window: ShellSurface,
 
     /// Window id.
     window_id: WindowId,
@@ -77,6 +85,9 @@ pub struct Window {
 
     /// The event sink to deliver sythetic events.
     window_events_sink: Arc<Mutex<EventSink>>,
+
+    /// Virtual input manager, to inject the input.
+    virtual_input_manager: Option<VirtualInputManager>,
 }
 
 impl Window {
@@ -96,6 +107,7 @@ impl Window {
             .as_ref()
             .map(|activation_state| activation_state.global().clone());
         let display = event_loop_window_target.connection.display();
+        let virtual_input_manager = state.virtual_input_manager.clone();
 
         let size: Size = attributes
             .inner_size
@@ -109,10 +121,20 @@ impl Window {
             WindowDecorations::RequestClient
         };
 
-        let window =
-            state
-                .xdg_shell
-                .create_window(surface.clone(), default_decorations, &queue_handle);
+        let window = match attributes.popup.as_ref() {
+            Some(popup) => ShellSurface::Popup(Self::create_popup(
+                &mut state,
+                &queue_handle,
+                surface.clone(),
+                popup,
+                size,
+            )?),
+            None => ShellSurface::Toplevel(state.xdg_shell.create_window(
+                surface.clone(),
+                default_decorations,
+                &queue_handle,
+            )),
+        };
 
         let mut window_state = WindowState::new(
             event_loop_window_target.connection.clone(),
@@ -132,13 +154,31 @@ impl Window {
         window_state.set_decorate(attributes.decorations);
 
         // Set the app_id.
-        if let Some(name) = attributes.platform_specific.name.map(|name| name.general) {
-            window.set_app_id(name);
+        if let Some((name, toplevel)) = attributes
+            .platform_specific
+            .name
+            .map(|name| name.general)
+            .zip(window.toplevel())
+        {
+            toplevel.set_app_id(name);
         }
 
         // Set the window title.
         window_state.set_title(attributes.title);
 
+        // Make the window transient for the foreign parent.
+        if let Some(handle) = attributes.platform_specific.wayland.parent_handle {
+            window_state.set_parent_handle(handle);
+        }
+
+        // Set the window icon.
+        if let Some(icon_name) = attributes.platform_specific.wayland.icon_name {
+            window_state.set_icon_name(Some(icon_name));
+        }
+        if let Some(window_icon) = attributes.window_icon {
+            window_state.set_window_icon(Some(window_icon.inner));
+        }
+
         // Set the min and max sizes. We must set the hints upon creating a window, so
         // we use the default `1.` scaling...
         let min_size = attributes.min_inner_size.map(|size| size.to_logical(1.));
@@ -149,23 +189,14 @@ impl Window {
         // Non-resizable implies that the min and max sizes are set to the same value.
         window_state.set_resizable(attributes.resizable);
 
-        // Set startup mode.
-        match attributes.fullscreen.map(Into::into) {
-            Some(Fullscreen::Exclusive(_)) => {
-                warn!("`Fullscreen::Exclusive` is ignored on Wayland");
-            }
-            Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
-                });
-
-                window.set_fullscreen(output.as_ref())
-            }
-            _ if attributes.maximized => window.set_maximized(),
-            _ => (),
-        };
+        // Set startup mode, popups can't be maximized or fullscreen.
+        if let Some(toplevel) = window.toplevel() {
+            match attributes.fullscreen.map(Into::into) {
+                Some(fullscreen) => apply_fullscreen(toplevel, &mut window_state, Some(fullscreen)),
+                None if attributes.maximized => toplevel.set_maximized(),
+                None => (),
+            };
+        }
 
         match attributes.cursor {
             Cursor::Icon(icon) => window_state.set_cursor(icon),
@@ -193,6 +224,7 @@ impl Window {
 
         let window_requests = WindowRequests {
             redraw_requested: AtomicBool::new(true),
+            frame_requested: AtomicBool::new(false),
             closed: AtomicBool::new(false),
         };
         let window_requests = Arc::new(window_requests);
@@ -240,8 +272,68 @@ impl Window {
             event_loop_awakener,
             window_requests,
             window_events_sink,
+            virtual_input_manager,
         })
     }
+
+    /// Create the `xdg_popup` for the surface, positioned relative to its parent.
+    fn create_popup(
+        state: &mut WinitState,
+        queue_handle: &QueueHandle<WinitState>,
+        surface: WlSurface,
+        popup: &PopupAttributes,
+        size: Size,
+    ) -> Result<Popup, RootOsError> {
+        let parent = match state.windows.get_mut().get(&popup.parent.0) {
+            Some(parent) => parent.clone(),
+            None => {
+                return Err(os_error!(OsError::Misc(
+                    "the popup parent is not a Wayland window"
+                )))
+            }
+        };
+        let parent = parent.lock().unwrap();
+
+        // The positioner operates in the logical coordinates of the parent.
+        let scale_factor = parent.scale_factor();
+        let size: LogicalSize<i32> = size.to_logical(scale_factor);
+        let (position, anchor_size) = popup.anchor_rect;
+        let position: LogicalPosition<i32> = position.to_logical(scale_factor);
+        let anchor_size: LogicalSize<i32> = anchor_size.to_logical(scale_factor);
+
+        let positioner = XdgPositioner::new(&state.xdg_shell)
+            .map_err(|_| os_error!(OsError::Misc("failed to create the popup positioner.")))?;
+        positioner.set_size(size.width.max(1), size.height.max(1));
+        positioner.set_anchor_rect(
+            position.x,
+            position.y,
+            anchor_size.width.max(1),
+            anchor_size.height.max(1),
+        );
+        positioner.set_anchor(popup.anchor.into());
+        positioner.set_gravity(popup.gravity.into());
+        // The bits match the ones of the `xdg_positioner`.
+        positioner.set_constraint_adjustment(popup.constraint_adjustment.bits());
+
+        let xdg_popup = Popup::from_surface(
+            Some(parent.window.xdg_surface()),
+            &positioner,
+            queue_handle,
+            surface,
+            &state.xdg_shell,
+        )
+        .map_err(|_| os_error!(OsError::Misc("failed to create the popup.")))?;
+
+        // The grab must be requested before the initial commit.
+        if popup.grab {
+            match parent.latest_button_serial() {
+                Some((seat, serial)) => xdg_popup.xdg_popup().grab(&seat, serial),
+                None => warn!("Failed to grab the input for the popup: no pointer on the parent"),
+            }
+        }
+
+        Ok(xdg_popup)
+    }
 }
 
 impl Window {
@@ -283,9 +375,7 @@ impl Window {
 
     #[inline]
     pub fn inner_size(&self) -> PhysicalSize<u32> {
-        let window_state = self.window_state.lock().unwrap();
-        let scale_factor = window_state.scale_factor();
-        super::logical_to_physical_rounded(window_state.inner_size(), scale_factor)
+        self.window_state.lock().unwrap().physical_inner_size()
     }
 
     #[inline]
@@ -304,6 +394,23 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        // The frame callback will be turned into the redraw request once it arrives.
+        self.window_requests
+            .frame_requested
+            .store(true, Ordering::Relaxed);
+
+        let mut window_state = self.window_state.lock().unwrap();
+        if window_state.frame_callback_state() != FrameCallbackState::Requested {
+            window_state.request_frame_callback();
+            // The frame callback is double buffered state, so commit it.
+            window_state.window.wl_surface().commit();
+            drop(window_state);
+            self.event_loop_awakener.ping();
+        }
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         self.window_state.lock().unwrap().request_frame_callback();
@@ -435,7 +542,25 @@ impl Window {
     pub fn set_window_level(&self, _level: WindowLevel) {}
 
     #[inline]
-    pub(crate) fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
+    pub(crate) fn set_window_icon(&self, window_icon: Option<PlatformIcon>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_window_icon(window_icon);
+    }
+
+    #[inline]
+    pub fn set_icon_name(&self, icon_name: Option<String>) {
+        self.window_state.lock().unwrap().set_icon_name(icon_name);
+    }
+
+    #[inline]
+    pub fn set_synthetic_key_repeat(&self, synthetic_key_repeat: bool) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_synthetic_key_repeat(synthetic_key_repeat);
+    }
 
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
@@ -445,7 +570,9 @@ impl Window {
             return;
         }
 
-        self.window.set_minimized();
+        if let Some(toplevel) = self.window.toplevel() {
+            toplevel.set_minimized();
+        }
     }
 
     #[inline]
@@ -461,49 +588,50 @@ impl Window {
 
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
+        let toplevel = match self.window.toplevel() {
+            Some(toplevel) => toplevel,
+            None => return,
+        };
+
         if maximized {
-            self.window.set_maximized()
+            toplevel.set_maximized()
         } else {
-            self.window.unset_maximized()
+            toplevel.unset_maximized()
         }
     }
 
     #[inline]
     pub(crate) fn fullscreen(&self) -> Option<Fullscreen> {
-        let is_fullscreen = self
-            .window_state
-            .lock()
-            .unwrap()
+        let window_state = self.window_state.lock().unwrap();
+        let is_fullscreen = window_state
             .last_configure
             .as_ref()
             .map(|last_configure| last_configure.is_fullscreen())
             .unwrap_or_default();
 
-        if is_fullscreen {
-            let current_monitor = self.current_monitor().map(PlatformMonitorHandle::Wayland);
-            Some(Fullscreen::Borderless(current_monitor))
-        } else {
-            None
+        if !is_fullscreen {
+            return None;
         }
+
+        if let Some(video_mode) = window_state.emulated_video_mode() {
+            return Some(Fullscreen::Exclusive(PlatformVideoModeHandle::Wayland(
+                video_mode.clone(),
+            )));
+        }
+
+        drop(window_state);
+        let current_monitor = self.current_monitor().map(PlatformMonitorHandle::Wayland);
+        Some(Fullscreen::Borderless(current_monitor))
     }
 
     #[inline]
     pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
-        match fullscreen {
-            Some(Fullscreen::Exclusive(_)) => {
-                warn!("`Fullscreen::Exclusive` is ignored on Wayland");
-            }
-            Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
-                });
-
-                self.window.set_fullscreen(output.as_ref())
-            }
-            None => self.window.unset_fullscreen(),
-        }
+        let toplevel = match self.window.toplevel() {
+            Some(toplevel) => toplevel,
+            None => return,
+        };
+
+        apply_fullscreen(toplevel, &mut self.window_state.lock().unwrap(), fullscreen);
     }
 
     #[inline]
@@ -566,6 +694,12 @@ impl Window {
         Ok(serial)
     }
 
+    pub fn export_handle(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
+        let serial = AsyncRequestSerial::get();
+        self.window_state.lock().unwrap().export_handle(serial)?;
+        Ok(serial)
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
@@ -588,6 +722,40 @@ impl Window {
         self.window_state.lock().unwrap().drag_window()
     }
 
+    pub fn inject_key(&self, key: PhysicalKey, state: ElementState) -> Result<(), ExternalError> {
+        let (manager, scancode) = match (
+            self.virtual_input_manager.as_ref(),
+            physicalkey_to_scancode(key),
+        ) {
+            (Some(manager), Some(scancode)) => (manager, scancode),
+            _ => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        // Wake-up the event loop to flush the requests.
+        manager
+            .inject_key(scancode, state)
+            .map(|_| self.event_loop_awakener.ping())
+    }
+
+    pub fn inject_pointer(&self, pointer: InjectedPointer) -> Result<(), ExternalError> {
+        let manager = match self.virtual_input_manager.as_ref() {
+            Some(manager) => manager,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        // The window doesn't know its position, so it can't move the pointer onto it.
+        let (button, state) = match pointer {
+            InjectedPointer::Moved(_) => {
+                return Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+            InjectedPointer::Button { button, state } => (button, state),
+        };
+
+        manager
+            .inject_button(winit_button_to_wayland(button), state)
+            .map(|_| self.event_loop_awakener.ping())
+    }
+
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
         let surface = self.window.wl_surface();
@@ -635,6 +803,20 @@ impl Window {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    #[inline]
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        self.window_state.lock().unwrap().set_ime_hints(hints);
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        let surrounding_text = SurroundingText::new(text, cursor, anchor);
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_ime_surrounding_text(surrounding_text);
+    }
+
     #[inline]
     pub fn focus_window(&self) {}
 
@@ -646,7 +828,15 @@ impl Window {
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         let data = self.window.wl_surface().data::<SurfaceData>()?;
-        data.outputs().next().map(MonitorHandle::new)
+        let gamma_control_manager = self
+            .window_state
+            .lock()
+            .unwrap()
+            .gamma_control_manager()
+            .cloned();
+        data.outputs()
+            .next()
+            .map(|output| MonitorHandle::new(output, gamma_control_manager))
     }
 
     #[inline]
@@ -717,8 +907,33 @@ impl Window {
         self.window_state.lock().unwrap().theme()
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        self.window_state.lock().unwrap().accent_color()
+    }
+
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    #[inline]
+    pub fn set_presentation_hint(&self, hint: PresentationHint) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_presentation_hint(hint);
+        // Request redraw, since the state is double buffered.
+        self.request_redraw();
+    }
+
+    #[inline]
+    pub fn set_content_type(&self, content_type: ContentType) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_content_type(content_type);
+        // Request redraw, since the state is double buffered.
+        self.request_redraw();
+    }
+
     #[inline]
     pub fn title(&self) -> String {
         self.window_state.lock().unwrap().title().to_owned()
@@ -732,6 +947,45 @@ impl Drop for Window {
     }
 }
 
+/// Make the toplevel fullscreen, emulating the video mode of the exclusive fullscreen.
+fn apply_fullscreen(
+    toplevel: &SctkWindow,
+    window_state: &mut WindowState,
+    fullscreen: Option<Fullscreen>,
+) {
+    let output = match fullscreen {
+        Some(Fullscreen::Exclusive(video_mode)) => {
+            let video_mode = match video_mode {
+                PlatformVideoModeHandle::Wayland(video_mode) => video_mode,
+                #[cfg(x11_platform)]
+                PlatformVideoModeHandle::X(_) => return,
+            };
+
+            let output = video_mode.monitor.proxy.clone();
+            if !window_state.set_emulated_video_mode(Some(video_mode)) {
+                warn!("`Fullscreen::Exclusive` requires `wp_viewporter`, using borderless instead");
+            }
+
+            Some(output)
+        }
+        Some(Fullscreen::Borderless(monitor)) => {
+            window_state.set_emulated_video_mode(None);
+            monitor.and_then(|monitor| match monitor {
+                PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
+                #[cfg(x11_platform)]
+                PlatformMonitorHandle::X(_) => None,
+            })
+        }
+        None => {
+            window_state.set_emulated_video_mode(None);
+            toplevel.unset_fullscreen();
+            return;
+        }
+    };
+
+    toplevel.set_fullscreen(output.as_ref());
+}
+
 /// The request from the window to the event loop.
 #[derive(Debug)]
 pub struct WindowRequests {
@@ -740,6 +994,9 @@ pub struct WindowRequests {
 
     /// Redraw Requested.
     pub redraw_requested: AtomicBool,
+
+    /// Redraw requested on the next frame callback.
+    pub frame_requested: AtomicBool,
 }
 
 impl WindowRequests {
@@ -750,6 +1007,10 @@ impl WindowRequests {
     pub fn take_redraw_requested(&self) -> bool {
         self.redraw_requested.swap(false, Ordering::Relaxed)
     }
+
+    pub fn take_frame_requested(&self) -> bool {
+        self.frame_requested.swap(false, Ordering::Relaxed)
+    }
 }
 
 impl TryFrom<&str> for Theme {
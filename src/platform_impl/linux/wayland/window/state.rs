@@ -13,34 +13,61 @@ use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
     DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
 };
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_v1::{
+    Type as WpContentType, WpContentTypeV1,
+};
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::{
+    PresentationHint as WpPresentationHint, WpTearingControlV1,
+};
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_exported_v2::ZxdgExportedV2;
+use sctk::reexports::protocols::xdg::foreign::zv2::client::zxdg_imported_v2::ZxdgImportedV2;
+use sctk::reexports::protocols::xdg::shell::client::xdg_positioner::{
+    Anchor as XdgAnchor, Gravity as XdgGravity,
+};
+use sctk::reexports::protocols::xdg::shell::client::xdg_surface::XdgSurface as XdgSurfaceProxy;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData, SurfaceDataExt};
 use sctk::seat::pointer::PointerDataExt;
+use sctk::shell::xdg::popup::{Popup, PopupConfigure};
 use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure};
 use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
-use sctk::shm::slot::SlotPool;
+use sctk::shm::slot::{Buffer, SlotPool};
 use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
 use crate::cursor::CustomCursor as RootCustomCursor;
-use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use crate::error::{ExternalError, NotSupportedError};
 use crate::event::WindowEvent;
+use crate::event_loop::AsyncRequestSerial;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
+use crate::platform_impl::wayland::output::VideoModeHandle;
+use crate::platform_impl::common::portal::Appearance;
+use crate::platform_impl::wayland::protocols::xdg_toplevel_icon::xdg_toplevel_icon_v1::XdgToplevelIconV1;
 use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::wlr_gamma_control::GammaControlManager;
+use crate::platform_impl::wayland::types::wp_content_type::ContentTypeManager;
+use crate::platform_impl::wayland::types::wp_tearing_control::TearingControlManager;
+use crate::platform_impl::wayland::types::xdg_foreign::{
+    XdgExportedData, XdgExporter, XdgImporter,
+};
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::{self, XdgToplevelIconManager};
 use crate::platform_impl::wayland::{logical_to_physical_rounded, make_wid};
-use crate::platform_impl::{PlatformCustomCursor, WindowId};
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
+use crate::platform_impl::{PlatformCustomCursor, PlatformIcon, WindowId};
+use crate::window::{
+    AccentColor, ContentType, CursorGrabMode, CursorIcon, ImeHints, ImePurpose, PopupAnchor,
+    PopupGravity, PresentationHint, ResizeDirection, Theme,
+};
 
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, WinitPointerData, ZwpTextInputV3Ext,
+    PointerConstraintsState, SurroundingText, WinitPointerData, ZwpTextInputV3Ext,
 };
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
 
@@ -52,6 +79,54 @@ pub type WinitFrame = sctk::shell::xdg::fallback_frame::FallbackFrame<WinitState
 // Minimum window inner size.
 const MIN_WINDOW_SIZE: LogicalSize<u32> = LogicalSize::new(2, 1);
 
+/// The xdg shell surface of the window.
+#[derive(Debug, Clone)]
+pub enum ShellSurface {
+    /// The regular, toplevel, window.
+    Toplevel(Window),
+
+    /// The popup attached to its parent window.
+    Popup(Popup),
+}
+
+impl ShellSurface {
+    /// The toplevel window, `None` for popups.
+    #[inline]
+    pub fn toplevel(&self) -> Option<&Window> {
+        match self {
+            Self::Toplevel(window) => Some(window),
+            Self::Popup(_) => None,
+        }
+    }
+
+    /// The popup, `None` for toplevel windows.
+    #[inline]
+    pub fn popup(&self) -> Option<&Popup> {
+        match self {
+            Self::Toplevel(_) => None,
+            Self::Popup(popup) => Some(popup),
+        }
+    }
+}
+
+impl WaylandSurface for ShellSurface {
+    fn wl_surface(&self) -> &WlSurface {
+        match self {
+            Self::Toplevel(window) => window.wl_surface(),
+            Self::Popup(popup) => popup.wl_surface(),
+        }
+    }
+}
+
+impl XdgSurface for ShellSurface {
+    fn xdg_surface(&self) -> &XdgSurfaceProxy {
+        match self {
+            Self::Toplevel(window) => window.xdg_surface(),
+            Self::Popup(popup) => popup.xdg_surface(),
+        }
+    }
+}
+
 /// The state of the window which is being updated from the [`WinitState`].
 pub struct WindowState {
     /// The connection to Wayland server.
@@ -95,6 +170,9 @@ pub struct WindowState {
     /// Whether the window has focus.
     has_focus: bool,
 
+    /// Whether winit repeats the held keys for the window.
+    synthetic_key_repeat: bool,
+
     /// The scale factor of the window.
     scale_factor: f64,
 
@@ -113,6 +191,12 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The current IME hints.
+    ime_hints: ImeHints,
+
+    /// The text around the cursor last set for the IME.
+    ime_surrounding_text: Option<SurroundingText>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
@@ -141,18 +225,57 @@ pub struct WindowState {
     /// The state of the frame callback.
     frame_callback_state: FrameCallbackState,
 
+    /// The time of the last received frame callback, not yet delivered to the user.
+    frame_time: Option<Duration>,
+
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+    tearing_control: Option<WpTearingControlV1>,
+    tearing_control_manager: Option<TearingControlManager>,
+    content_type: Option<WpContentTypeV1>,
+    content_type_manager: Option<ContentTypeManager>,
+    toplevel_icon_manager: Option<XdgToplevelIconManager>,
+
+    /// The icon set on the toplevel and the buffers backing it.
+    toplevel_icon: Option<(XdgToplevelIconV1, Vec<Buffer>)>,
+
+    /// The user provided window icon.
+    window_icon: Option<PlatformIcon>,
+
+    /// The name of the window icon from the icon theme.
+    icon_name: Option<String>,
+
+    xdg_exporter: Option<XdgExporter>,
+    xdg_importer: Option<XdgImporter>,
+    gamma_control_manager: Option<GammaControlManager>,
+
+    /// The appearance of the desktop, for the windows following it.
+    appearance: Appearance,
+
+    /// The latest export of the window, the previous ones are destroyed.
+    exported: Option<ZxdgExportedV2>,
+
+    /// The foreign toplevel the window is transient for.
+    imported_parent: Option<ZxdgImportedV2>,
+
+    /// Whether the popup received its initial configure.
+    popup_configured: bool,
+
+    /// The video mode emulated by scaling the window contents with the viewport.
+    emulated_video_mode: Option<VideoModeHandle>,
+
+    /// Whether the emulated video mode changed since the last configure.
+    emulated_video_mode_changed: bool,
 
     /// Whether the client side decorations have pending move operations.
     ///
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
-    /// The underlying SCTK window.
-    pub window: Window,
+    /// The underlying SCTK shell surface.
+    pub window: ShellSurface,
 }
 
 impl WindowState {
@@ -162,7 +285,7 @@ impl WindowState {
         queue_handle: &QueueHandle<WinitState>,
         winit_state: &WinitState,
         initial_size: Size,
-        window: Window,
+        window: ShellSurface,
         theme: Option<Theme>,
     ) -> Self {
         let compositor = winit_state.compositor_state.clone();
@@ -179,6 +302,20 @@ impl WindowState {
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            tearing_control: None,
+            tearing_control_manager: winit_state.tearing_control_manager.clone(),
+            content_type: None,
+            content_type_manager: winit_state.content_type_manager.clone(),
+            toplevel_icon_manager: winit_state.xdg_toplevel_icon_manager.clone(),
+            toplevel_icon: None,
+            window_icon: None,
+            icon_name: None,
+            xdg_exporter: winit_state.xdg_exporter.clone(),
+            xdg_importer: winit_state.xdg_importer.clone(),
+            gamma_control_manager: winit_state.gamma_control_manager.clone(),
+            appearance: winit_state.appearance,
+            exported: None,
+            imported_parent: None,
             compositor,
             connection,
             csd_fails: false,
@@ -189,11 +326,18 @@ impl WindowState {
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
+            frame_time: None,
             has_focus: false,
+            synthetic_key_repeat: winit_state.synthetic_key_repeat,
             has_pending_move: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_hints: ImeHints::empty(),
+            ime_surrounding_text: None,
             last_configure: None,
+            popup_configured: false,
+            emulated_video_mode: None,
+            emulated_video_mode_changed: false,
             max_inner_size: None,
             min_inner_size: MIN_WINDOW_SIZE,
             pointer_constraints,
@@ -235,8 +379,14 @@ impl WindowState {
     }
 
     /// The frame callback was received, but not yet sent to the user.
-    pub fn frame_callback_received(&mut self) {
+    pub fn frame_callback_received(&mut self, time: u32) {
         self.frame_callback_state = FrameCallbackState::Received;
+        self.frame_time = Some(Duration::from_millis(time as u64));
+    }
+
+    /// Take the time of the last frame callback, if it wasn't yet sent to the user.
+    pub fn take_frame_time(&mut self) -> Option<Duration> {
+        self.frame_time.take()
     }
 
     /// Reset the frame callbacks state.
@@ -271,20 +421,24 @@ impl WindowState {
             self.stateless_size = self.size;
         }
 
-        if let Some(subcompositor) = subcompositor.as_ref().filter(|_| {
-            configure.decoration_mode == DecorationMode::Client
-                && self.frame.is_none()
-                && !self.csd_fails
-        }) {
+        if let Some((subcompositor, window)) = subcompositor
+            .as_ref()
+            .zip(self.window.toplevel())
+            .filter(|_| {
+                configure.decoration_mode == DecorationMode::Client
+                    && self.frame.is_none()
+                    && !self.csd_fails
+            })
+        {
             match WinitFrame::new(
-                &self.window,
+                window,
                 shm,
                 #[cfg(feature = "sctk-adwaita")]
                 self.compositor.clone(),
                 subcompositor.clone(),
                 self.queue_handle.clone(),
                 #[cfg(feature = "sctk-adwaita")]
-                into_sctk_adwaita_config(self.theme),
+                into_sctk_adwaita_config(self.theme()),
             ) {
                 Ok(mut frame) => {
                     frame.set_title(&self.title);
@@ -359,6 +513,19 @@ impl WindowState {
             .as_ref()
             .map(|configure| configure.state);
 
+        // Stop the emulation of the video mode and restore the gamma ramps once the window
+        // leaves the fullscreen, the ramps set while it's fullscreen belong to it.
+        let was_fullscreen =
+            matches!(old_state, Some(state) if state.contains(XdgWindowState::FULLSCREEN));
+        if was_fullscreen && !new_state.contains(XdgWindowState::FULLSCREEN) {
+            self.set_emulated_video_mode(None);
+            self.reset_gamma_ramps();
+        } else if !was_fullscreen && new_state.contains(XdgWindowState::FULLSCREEN) {
+            if let Some(gamma_control_manager) = self.gamma_control_manager.as_ref() {
+                gamma_control_manager.add_fullscreen_surface(self.window.wl_surface());
+            }
+        }
+
         let state_change_requires_resize = old_state
             .map(|old_state| {
                 !old_state
@@ -372,7 +539,11 @@ impl WindowState {
         // NOTE: Set the configure before doing a resize, since we query it during it.
         self.last_configure = Some(configure);
 
-        if state_change_requires_resize || new_size != self.inner_size() {
+        let emulated_video_mode_changed = std::mem::take(&mut self.emulated_video_mode_changed);
+        if state_change_requires_resize
+            || emulated_video_mode_changed
+            || new_size != self.inner_size()
+        {
             self.resize(new_size);
             true
         } else {
@@ -409,9 +580,46 @@ impl WindowState {
         !(configure.is_maximized() || configure.is_fullscreen() || configure.is_tiled())
     }
 
+    /// Apply the configure of the popup, returning whether it was resized.
+    pub fn configure_popup(&mut self, configure: PopupConfigure) -> bool {
+        if let Some(initial_size) = self.initial_size.take() {
+            self.size = initial_size.to_logical(self.scale_factor());
+            self.stateless_size = self.size;
+        }
+
+        let new_size = LogicalSize::new(
+            configure.width.max(1) as u32,
+            configure.height.max(1) as u32,
+        );
+
+        // Always resize on the initial configure to set the window geometry.
+        let initial_configure = !std::mem::replace(&mut self.popup_configured, true);
+        if initial_configure || new_size != self.size {
+            self.resize(new_size);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The seat and the serial of the latest button press on the window.
+    pub fn latest_button_serial(&self) -> Option<(WlSeat, u32)> {
+        self.pointers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|pointer| {
+                let data = pointer.winit_data();
+                (data.seat().clone(), data.latest_button_serial())
+            })
+            .max_by_key(|(_, serial)| *serial)
+    }
+
     /// Start interacting drag resize.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
-        let xdg_toplevel = self.window.xdg_toplevel();
+        let xdg_toplevel = match self.window.toplevel() {
+            Some(window) => window.xdg_toplevel(),
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
 
         // TODO(kchibisov) handle touch serials.
         self.apply_on_poiner(|_, data| {
@@ -425,7 +633,10 @@ impl WindowState {
 
     /// Start the window drag.
     pub fn drag_window(&self) -> Result<(), ExternalError> {
-        let xdg_toplevel = self.window.xdg_toplevel();
+        let xdg_toplevel = match self.window.toplevel() {
+            Some(window) => window.xdg_toplevel(),
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
         // TODO(kchibisov) handle touch serials.
         self.apply_on_poiner(|_, data| {
             let serial = data.latest_button_serial();
@@ -448,10 +659,11 @@ impl WindowState {
         window_id: WindowId,
         updates: &mut Vec<WindowCompositorUpdate>,
     ) -> Option<bool> {
+        let window = self.window.toplevel()?;
         match self.frame.as_mut()?.on_click(timestamp, click, pressed)? {
-            FrameAction::Minimize => self.window.set_minimized(),
-            FrameAction::Maximize => self.window.set_maximized(),
-            FrameAction::UnMaximize => self.window.unset_maximized(),
+            FrameAction::Minimize => window.set_minimized(),
+            FrameAction::Maximize => window.set_maximized(),
+            FrameAction::UnMaximize => window.unset_maximized(),
             FrameAction::Close => WinitState::queue_close(updates, window_id),
             FrameAction::Move => self.has_pending_move = Some(serial),
             FrameAction::Resize(edge) => {
@@ -467,9 +679,9 @@ impl WindowState {
                     ResizeEdge::BottomRight => XdgResizeEdge::BottomRight,
                     _ => return None,
                 };
-                self.window.resize(seat, serial, edge);
+                window.resize(seat, serial, edge);
             }
-            FrameAction::ShowMenu(x, y) => self.window.show_window_menu(seat, serial, (x, y)),
+            FrameAction::ShowMenu(x, y) => window.show_window_menu(seat, serial, (x, y)),
             _ => (),
         };
 
@@ -498,8 +710,13 @@ impl WindowState {
             let cursor = frame.click_point_moved(timestamp, &surface.id(), x, y);
             // If we have a cursor change, that means that cursor is over the decorations,
             // so try to apply move.
-            if let Some(serial) = cursor.is_some().then_some(serial).flatten() {
-                self.window.move_(seat, serial);
+            if let Some((serial, window)) = cursor
+                .is_some()
+                .then_some(serial)
+                .flatten()
+                .zip(self.window.toplevel())
+            {
+                window.move_(seat, serial);
                 None
             } else {
                 cursor
@@ -543,6 +760,12 @@ impl WindowState {
         self.has_focus
     }
 
+    /// Whether winit repeats the held keys for the window.
+    #[inline]
+    pub fn synthetic_key_repeat(&self) -> bool {
+        self.synthetic_key_repeat
+    }
+
     /// Whether the IME is allowed.
     #[inline]
     pub fn ime_allowed(&self) -> bool {
@@ -555,10 +778,78 @@ impl WindowState {
         self.size
     }
 
+    /// Get the physical size of the window, which is the size of the emulated video mode when
+    /// there's one.
+    #[inline]
+    pub fn physical_inner_size(&self) -> PhysicalSize<u32> {
+        match self.emulated_video_mode.as_ref() {
+            Some(video_mode) => video_mode.size(),
+            None => logical_to_physical_rounded(self.inner_size(), self.scale_factor()),
+        }
+    }
+
+    /// Convert the surface local position into the physical position inside the window.
+    #[inline]
+    pub fn surface_to_physical(&self, position: LogicalPosition<f64>) -> PhysicalPosition<f64> {
+        surface_to_physical(
+            position,
+            self.size,
+            self.scale_factor(),
+            self.emulated_video_mode.as_ref().map(VideoModeHandle::size),
+        )
+    }
+
+    /// The emulated video mode of the window.
+    #[inline]
+    pub fn emulated_video_mode(&self) -> Option<&VideoModeHandle> {
+        self.emulated_video_mode.as_ref()
+    }
+
+    /// Emulate the video mode by scaling the buffer of its size to the window with the viewport.
+    ///
+    /// Returns `false` when the emulation isn't supported.
+    pub fn set_emulated_video_mode(&mut self, video_mode: Option<VideoModeHandle>) -> bool {
+        if video_mode.is_some() && self.viewport.is_none() {
+            return false;
+        }
+
+        if self.emulated_video_mode == video_mode {
+            return true;
+        }
+
+        self.emulated_video_mode = video_mode;
+        self.emulated_video_mode_changed = true;
+
+        // NOTE: The buffer of the video mode size isn't necessarily divisible by the buffer scale,
+        // the viewport is what scales it.
+        if self.fractional_scale.is_none() {
+            let scale = match self.emulated_video_mode {
+                Some(_) => 1,
+                None => self.scale_factor as i32,
+            };
+            let _ = self.window.set_buffer_scale(scale as _);
+        }
+
+        true
+    }
+
+    /// The gamma control manager, for the monitor handles.
+    #[inline]
+    pub fn gamma_control_manager(&self) -> Option<&GammaControlManager> {
+        self.gamma_control_manager.as_ref()
+    }
+
+    /// Restore the gamma ramps changed on the outputs of the window while it was fullscreen.
+    fn reset_gamma_ramps(&self) {
+        if let Some(gamma_control_manager) = self.gamma_control_manager.as_ref() {
+            gamma_control_manager.remove_fullscreen_surface(self.window.wl_surface());
+        }
+    }
+
     /// Whether the window received initial configure event from the compositor.
     #[inline]
     pub fn is_configured(&self) -> bool {
-        self.last_configure.is_some()
+        self.last_configure.is_some() || self.popup_configured
     }
 
     #[inline]
@@ -656,7 +947,7 @@ impl WindowState {
             self.resize(inner_size.to_logical(self.scale_factor()))
         }
 
-        logical_to_physical_rounded(self.inner_size(), self.scale_factor())
+        self.physical_inner_size()
     }
 
     /// Resize the window to the new inner size.
@@ -801,7 +1092,9 @@ impl WindowState {
             .unwrap_or(size);
 
         self.min_inner_size = size;
-        self.window.set_min_size(Some(size.into()));
+        if let Some(window) = self.window.toplevel() {
+            window.set_min_size(Some(size.into()));
+        }
     }
 
     /// Set maximum inner window size.
@@ -814,22 +1107,48 @@ impl WindowState {
         });
 
         self.max_inner_size = size;
-        self.window.set_max_size(size.map(Into::into));
+        if let Some(window) = self.window.toplevel() {
+            window.set_max_size(size.map(Into::into));
+        }
     }
 
     /// Set the CSD theme.
     pub fn set_theme(&mut self, theme: Option<Theme>) {
         self.theme = theme;
-        #[cfg(feature = "sctk-adwaita")]
-        if let Some(frame) = self.frame.as_mut() {
-            frame.set_config(into_sctk_adwaita_config(theme))
-        }
+        self.reload_frame_theme();
     }
 
-    /// The current theme for CSD decorations.
+    /// The current theme for CSD decorations, following the desktop when it's not set.
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
-        self.theme
+        self.theme.or(self.appearance.theme)
+    }
+
+    /// The accent color of the desktop.
+    #[inline]
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        self.appearance.accent_color
+    }
+
+    /// Update the appearance of the desktop, returning whether the theme of the window changed.
+    pub fn set_appearance(&mut self, appearance: Appearance) -> bool {
+        let prev_theme = self.theme();
+        self.appearance = appearance;
+        if self.theme() == prev_theme {
+            return false;
+        }
+
+        self.reload_frame_theme();
+        true
+    }
+
+    fn reload_frame_theme(&mut self) {
+        #[cfg(feature = "sctk-adwaita")]
+        if let Some(frame) = self.frame.as_mut() {
+            frame.set_config(into_sctk_adwaita_config(
+                self.theme.or(self.appearance.theme),
+            ))
+        }
     }
 
     /// Set the cursor grabbing state on the top-level.
@@ -889,11 +1208,16 @@ impl WindowState {
     }
 
     pub fn show_window_menu(&self, position: LogicalPosition<u32>) {
+        let window = match self.window.toplevel() {
+            Some(window) => window,
+            None => return,
+        };
+
         // TODO(kchibisov) handle touch serials.
         self.apply_on_poiner(|_, data| {
             let serial = data.latest_button_serial();
             let seat = data.seat();
-            self.window.show_window_menu(seat, serial, position.into());
+            window.show_window_menu(seat, serial, position.into());
         });
     }
 
@@ -944,19 +1268,18 @@ impl WindowState {
 
         self.decorate = decorate;
 
-        match self
+        let decoration_mode = self
             .last_configure
             .as_ref()
-            .map(|configure| configure.decoration_mode)
-        {
-            Some(DecorationMode::Server) if !self.decorate => {
+            .map(|configure| configure.decoration_mode);
+        match (self.window.toplevel(), decoration_mode) {
+            (Some(window), Some(DecorationMode::Server)) if !self.decorate => {
                 // To disable decorations we should request client and hide the frame.
-                self.window
-                    .request_decoration_mode(Some(DecorationMode::Client))
+                window.request_decoration_mode(Some(DecorationMode::Client))
+            }
+            (Some(window), _) if self.decorate => {
+                window.request_decoration_mode(Some(DecorationMode::Server))
             }
-            _ if self.decorate => self
-                .window
-                .request_decoration_mode(Some(DecorationMode::Server)),
             _ => (),
         }
 
@@ -975,6 +1298,11 @@ impl WindowState {
         self.has_focus = has_focus;
     }
 
+    #[inline]
+    pub fn set_synthetic_key_repeat(&mut self, synthetic_key_repeat: bool) {
+        self.synthetic_key_repeat = synthetic_key_repeat;
+    }
+
     /// Returns `true` if the requested state was applied.
     pub fn set_ime_allowed(&mut self, allowed: bool) -> bool {
         self.ime_allowed = allowed;
@@ -984,7 +1312,10 @@ impl WindowState {
             applied = true;
             if allowed {
                 text_input.enable();
-                text_input.set_content_type_by_purpose(self.ime_purpose);
+                text_input.set_content_type_by_purpose(self.ime_purpose, self.ime_hints);
+                if let Some(surrounding_text) = self.ime_surrounding_text.as_ref() {
+                    text_input.set_surrounding(surrounding_text);
+                }
             } else {
                 text_input.disable();
             }
@@ -1012,7 +1343,7 @@ impl WindowState {
         self.ime_purpose = purpose;
 
         for text_input in &self.text_inputs {
-            text_input.set_content_type_by_purpose(purpose);
+            text_input.set_content_type_by_purpose(purpose, self.ime_hints);
             text_input.commit();
         }
     }
@@ -1022,13 +1353,45 @@ impl WindowState {
         self.ime_purpose
     }
 
+    /// Set the IME hints.
+    pub fn set_ime_hints(&mut self, hints: ImeHints) {
+        self.ime_hints = hints;
+
+        for text_input in &self.text_inputs {
+            text_input.set_content_type_by_purpose(self.ime_purpose, hints);
+            text_input.commit();
+        }
+    }
+
+    /// Get the IME hints.
+    pub fn ime_hints(&self) -> ImeHints {
+        self.ime_hints
+    }
+
+    /// Set the text around the cursor for the IME.
+    pub fn set_ime_surrounding_text(&mut self, surrounding_text: SurroundingText) {
+        if self.ime_allowed {
+            for text_input in &self.text_inputs {
+                text_input.set_surrounding(&surrounding_text);
+                text_input.commit();
+            }
+        }
+
+        self.ime_surrounding_text = Some(surrounding_text);
+    }
+
+    /// Get the text around the cursor for the IME.
+    pub fn ime_surrounding_text(&self) -> Option<&SurroundingText> {
+        self.ime_surrounding_text.as_ref()
+    }
+
     /// Set the scale factor for the given window.
     #[inline]
     pub fn set_scale_factor(&mut self, scale_factor: f64) {
         self.scale_factor = scale_factor;
 
         // NOTE: When fractional scaling is not used update the buffer scale.
-        if self.fractional_scale.is_none() {
+        if self.fractional_scale.is_none() && self.emulated_video_mode.is_none() {
             let _ = self.window.set_buffer_scale(self.scale_factor as _);
         }
 
@@ -1057,6 +1420,182 @@ impl WindowState {
         }
     }
 
+    /// Set the presentation hint of the surface.
+    pub fn set_presentation_hint(&mut self, hint: PresentationHint) {
+        if self.tearing_control.is_none() {
+            match self.tearing_control_manager.as_ref() {
+                Some(manager) => {
+                    self.tearing_control =
+                        Some(manager.tearing_control(self.window.wl_surface(), &self.queue_handle));
+                }
+                None => {
+                    info!("Tearing control manager unavailable, unable to change presentation");
+                    return;
+                }
+            }
+        }
+
+        let hint = match hint {
+            PresentationHint::Vsync => WpPresentationHint::Vsync,
+            PresentationHint::Async => WpPresentationHint::Async,
+        };
+        self.tearing_control
+            .as_ref()
+            .unwrap()
+            .set_presentation_hint(hint);
+    }
+
+    /// Set the content type of the surface.
+    pub fn set_content_type(&mut self, content_type: ContentType) {
+        if self.content_type.is_none() {
+            match self.content_type_manager.as_ref() {
+                Some(manager) => {
+                    self.content_type =
+                        Some(manager.content_type(self.window.wl_surface(), &self.queue_handle));
+                }
+                None => {
+                    info!("Content type manager unavailable, unable to change content type");
+                    return;
+                }
+            }
+        }
+
+        let content_type = match content_type {
+            ContentType::None => WpContentType::None,
+            ContentType::Photo => WpContentType::Photo,
+            ContentType::Video => WpContentType::Video,
+            ContentType::Game => WpContentType::Game,
+        };
+        self.content_type
+            .as_ref()
+            .unwrap()
+            .set_content_type(content_type);
+    }
+
+    /// Set the window icon.
+    #[inline]
+    pub(crate) fn set_window_icon(&mut self, window_icon: Option<PlatformIcon>) {
+        self.window_icon = window_icon;
+        self.reload_icon();
+    }
+
+    /// Set the name of the window icon from the icon theme.
+    #[inline]
+    pub fn set_icon_name(&mut self, icon_name: Option<String>) {
+        self.icon_name = icon_name;
+        self.reload_icon();
+    }
+
+    /// Upload the window icon and the icon name to the compositor.
+    pub fn reload_icon(&mut self) {
+        let xdg_toplevel = match self.window.toplevel() {
+            Some(window) => window.xdg_toplevel(),
+            None => return,
+        };
+
+        let manager = match self.toplevel_icon_manager.as_ref() {
+            Some(manager) => manager,
+            None => {
+                info!("Toplevel icon manager unavailable, unable to set the window icon");
+                return;
+            }
+        };
+
+        let icon = manager.create_icon(&self.queue_handle);
+        if let Some(icon_name) = self.icon_name.as_ref() {
+            icon.set_name(icon_name.clone());
+        }
+
+        let mut buffers = Vec::new();
+        if let Some(window_icon) = self.window_icon.as_ref() {
+            let mut pool = self.custom_cursor_pool.lock().unwrap();
+            let scale = self.scale_factor.ceil() as i32;
+            let scales: &[i32] = if scale > 1 { &[1, scale] } else { &[1] };
+            for size in manager.icon_sizes(window_icon) {
+                for &scale in scales {
+                    if let Some(buffer) =
+                        xdg_toplevel_icon::create_icon_buffer(&mut pool, window_icon, size * scale)
+                    {
+                        icon.add_buffer(buffer.wl_buffer(), scale);
+                        buffers.push(buffer);
+                    }
+                }
+            }
+        }
+
+        // An icon without a name and buffers is invalid, reset to the default one.
+        let icon = if self.icon_name.is_none() && buffers.is_empty() {
+            icon.destroy();
+            None
+        } else {
+            Some((icon, buffers))
+        };
+
+        manager.set_icon(xdg_toplevel, icon.as_ref().map(|(icon, _)| icon));
+
+        // The old icon is no longer referenced by the toplevel.
+        if let Some((old_icon, _)) = std::mem::replace(&mut self.toplevel_icon, icon) {
+            old_icon.destroy();
+        }
+    }
+
+    /// Export the window to get a handle for other processes.
+    ///
+    /// The handle is delivered with the `WindowEvent::ExportHandleDone`.
+    pub fn export_handle(&mut self, serial: AsyncRequestSerial) -> Result<(), NotSupportedError> {
+        let exporter = match self.xdg_exporter.as_ref() {
+            Some(exporter) => exporter,
+            None => return Err(NotSupportedError::new()),
+        };
+
+        let surface = self.window.wl_surface();
+        let data = XdgExportedData {
+            window_id: make_wid(surface),
+            serial,
+        };
+        let exported = exporter.export_toplevel(surface, &self.queue_handle, data);
+
+        // The handle of the previous export is no longer valid.
+        if let Some(old_exported) = self.exported.replace(exported) {
+            old_exported.destroy();
+        }
+
+        Ok(())
+    }
+
+    /// Make the window transient for the foreign toplevel exported with the given handle.
+    pub fn set_parent_handle(&mut self, handle: String) {
+        let importer = match self.xdg_importer.as_ref() {
+            Some(importer) => importer,
+            None => {
+                info!("Xdg importer unavailable, unable to set the foreign parent");
+                return;
+            }
+        };
+
+        let imported = importer.import_toplevel(handle, &self.queue_handle);
+        imported.set_parent_of(self.window.wl_surface());
+
+        if let Some(imported) = self.imported_parent.replace(imported) {
+            imported.destroy();
+        }
+    }
+
+    /// Drop the imported foreign parent when it was destroyed by the compositor.
+    ///
+    /// Returns `true` when it was the parent of this window.
+    pub fn foreign_parent_destroyed(&mut self, imported: &ZxdgImportedV2) -> bool {
+        if self.imported_parent.as_ref() != Some(imported) {
+            return false;
+        }
+
+        if let Some(imported) = self.imported_parent.take() {
+            imported.destroy();
+        }
+
+        true
+    }
+
     /// Set the window title to a new value.
     ///
     /// This will autmatically truncate the title to something meaningfull.
@@ -1076,7 +1615,9 @@ impl WindowState {
             frame.set_title(&title);
         }
 
-        self.window.set_title(&title);
+        if let Some(window) = self.window.toplevel() {
+            window.set_title(&title);
+        }
         self.title = title;
     }
 
@@ -1112,6 +1653,10 @@ impl WindowState {
 
 impl Drop for WindowState {
     fn drop(&mut self) {
+        if matches!(self.last_configure.as_ref(), Some(configure) if configure.is_fullscreen()) {
+            self.reset_gamma_ramps();
+        }
+
         if let Some(blur) = self.blur.take() {
             blur.release();
         }
@@ -1124,6 +1669,26 @@ impl Drop for WindowState {
             viewport.destroy();
         }
 
+        if let Some(tearing_control) = self.tearing_control.take() {
+            tearing_control.destroy();
+        }
+
+        if let Some(content_type) = self.content_type.take() {
+            content_type.destroy();
+        }
+
+        if let Some((icon, _)) = self.toplevel_icon.take() {
+            icon.destroy();
+        }
+
+        if let Some(exported) = self.exported.take() {
+            exported.destroy();
+        }
+
+        if let Some(imported) = self.imported_parent.take() {
+            imported.destroy();
+        }
+
         // NOTE: the wl_surface used by the window is being cleaned up when
         // dropping SCTK `Window`.
     }
@@ -1175,6 +1740,55 @@ impl From<ResizeDirection> for XdgResizeEdge {
     }
 }
 
+impl From<PopupAnchor> for XdgAnchor {
+    fn from(value: PopupAnchor) -> Self {
+        match value {
+            PopupAnchor::None => XdgAnchor::None,
+            PopupAnchor::Top => XdgAnchor::Top,
+            PopupAnchor::Bottom => XdgAnchor::Bottom,
+            PopupAnchor::Left => XdgAnchor::Left,
+            PopupAnchor::Right => XdgAnchor::Right,
+            PopupAnchor::TopLeft => XdgAnchor::TopLeft,
+            PopupAnchor::BottomLeft => XdgAnchor::BottomLeft,
+            PopupAnchor::TopRight => XdgAnchor::TopRight,
+            PopupAnchor::BottomRight => XdgAnchor::BottomRight,
+        }
+    }
+}
+
+impl From<PopupGravity> for XdgGravity {
+    fn from(value: PopupGravity) -> Self {
+        match value {
+            PopupGravity::None => XdgGravity::None,
+            PopupGravity::Top => XdgGravity::Top,
+            PopupGravity::Bottom => XdgGravity::Bottom,
+            PopupGravity::Left => XdgGravity::Left,
+            PopupGravity::Right => XdgGravity::Right,
+            PopupGravity::TopLeft => XdgGravity::TopLeft,
+            PopupGravity::BottomLeft => XdgGravity::BottomLeft,
+            PopupGravity::TopRight => XdgGravity::TopRight,
+            PopupGravity::BottomRight => XdgGravity::BottomRight,
+        }
+    }
+}
+
+/// Convert the surface local position into the physical position inside the window, scaling it
+/// to the emulated video mode when there's one.
+fn surface_to_physical(
+    position: LogicalPosition<f64>,
+    surface_size: LogicalSize<u32>,
+    scale_factor: f64,
+    emulated_size: Option<PhysicalSize<u32>>,
+) -> PhysicalPosition<f64> {
+    match emulated_size {
+        Some(size) => PhysicalPosition::new(
+            position.x * size.width as f64 / surface_size.width as f64,
+            position.y * size.height as f64 / surface_size.height as f64,
+        ),
+        None => position.to_physical(scale_factor),
+    }
+}
+
 // NOTE: Rust doesn't allow `From<Option<Theme>>`.
 #[cfg(feature = "sctk-adwaita")]
 fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
@@ -1184,3 +1798,36 @@ fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
         None => sctk_adwaita::FrameConfig::auto(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touch_location_with_emulated_video_mode() {
+        // A touch point in the middle of a 1920x1080 surface at the scale of 2.
+        let location = LogicalPosition::new(960., 540.);
+        let surface_size = LogicalSize::new(1920, 1080);
+
+        assert_eq!(
+            surface_to_physical(location, surface_size, 2., None),
+            PhysicalPosition::new(1920., 1080.)
+        );
+
+        // The 800x600 emulated video mode is scaled to the surface, whatever the scale factor.
+        let emulated_size = Some(PhysicalSize::new(800, 600));
+        assert_eq!(
+            surface_to_physical(location, surface_size, 2., emulated_size),
+            PhysicalPosition::new(400., 300.)
+        );
+        assert_eq!(
+            surface_to_physical(
+                LogicalPosition::new(1920., 0.),
+                surface_size,
+                2.,
+                emulated_size
+            ),
+            PhysicalPosition::new(800., 0.)
+        );
+    }
+}
@@ -34,6 +34,7 @@ macro_rules! atom_manager {
 atom_manager! {
     // General Use Atoms
     CARD32,
+    Edid: b"EDID",
     UTF8_STRING,
     WM_CHANGE_STATE,
     WM_CLIENT_MACHINE,
@@ -43,6 +44,8 @@ atom_manager! {
     XIM_SERVERS,
 
     // Assorted ICCCM Atoms
+    _NET_WM_BYPASS_COMPOSITOR,
+    _NET_WM_FRAME_DRAWN,
     _NET_WM_ICON,
     _NET_WM_MOVERESIZE,
     _NET_WM_NAME,
@@ -55,6 +58,8 @@ atom_manager! {
     _NET_WM_STATE_HIDDEN,
     _NET_WM_STATE_MAXIMIZED_HORZ,
     _NET_WM_STATE_MAXIMIZED_VERT,
+    _NET_WM_SYNC_REQUEST,
+    _NET_WM_SYNC_REQUEST_COUNTER,
     _NET_WM_WINDOW_TYPE,
 
     // Activation atoms.
@@ -100,7 +105,13 @@ atom_manager! {
     _NET_FRAME_EXTENTS,
     _NET_SUPPORTED,
     _NET_SUPPORTING_WM_CHECK,
-    _XEMBED
+    _XEMBED,
+    _XKB_RULES_NAMES,
+    ScrollLock: b"Scroll Lock",
+
+    // XSETTINGS Atoms
+    Manager: b"MANAGER",
+    _XSETTINGS_SETTINGS
 }
 
 impl Index<AtomName> for Atoms {
@@ -1,12 +1,15 @@
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
-    os::raw::{c_char, c_int, c_long, c_ulong},
+    mem::MaybeUninit,
+    os::raw::{c_char, c_int, c_long, c_uint, c_ulong},
     rc::Rc,
     slice,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
+use log::warn;
 use x11rb::x11_utils::Serialize;
 use x11rb::{
     protocol::{
@@ -18,15 +21,21 @@ use x11rb::{
 
 use super::{
     atoms::*, ffi, get_xtarget, mkdid, mkwid, util, CookieResultExt, Device, DeviceId, DeviceInfo,
-    Dnd, DndState, GenericEventCookie, ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
+    Dnd, DndState, GenericEventCookie, ImeReceiver, MonitorHandle, ScrollOrientation,
+    UnownedWindow, WindowId,
 };
 
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
-    event::{DeviceEvent, ElementState, Event, Ime, RawKeyEvent, TouchPhase, WindowEvent},
-    event_loop::EventLoopWindowTarget as RootELW,
-    keyboard::ModifiersState,
+    event::{
+        DeviceEvent, ElementState, Event, Ime, Modifiers, RawKeyEvent, TouchPhase, WindowEvent,
+    },
+    event_loop::{EventLoopWindowTarget as RootELW, SystemSettings},
+    keyboard::{KeyboardLayoutInfo, LockState},
+    monitor::MonitorHandle as RootMonitorHandle,
     platform_impl::platform::common::{keymap, xkb_state::KbdState},
+    platform_impl::MonitorHandle as PlatformMonitorHandle,
+    window::Theme,
 };
 use crate::{
     event::InnerSizeWriter,
@@ -34,7 +43,10 @@ use crate::{
 };
 
 /// The X11 documentation states: "Keycodes lie in the inclusive range `[8, 255]`".
-const KEYCODE_OFFSET: u8 = 8;
+pub(super) const KEYCODE_OFFSET: u8 = 8;
+
+/// The `QueuedAfterReading` mode of `XEventsQueued`, missing from the bindings.
+const QUEUED_AFTER_READING: c_int = 1;
 
 pub(super) struct EventProcessor {
     pub(super) dnd: Dnd,
@@ -53,12 +65,18 @@ pub(super) struct EventProcessor {
     //
     // Used to detect key repeats.
     pub(super) held_key_press: Option<u32>,
+    /// The key of the global hotkey being held, to ignore its repeats.
+    pub(super) held_hotkey: Option<xproto::Keycode>,
     pub(super) first_touch: Option<u64>,
     // Currently focused window belonging to this process
     pub(super) active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
-    pub(super) modifiers: Cell<ModifiersState>,
+    pub(super) modifiers: Cell<Modifiers>,
+    /// Whether the scroll lock indicator of the server is on.
+    pub(super) scroll_lock: bool,
     pub(super) is_composing: bool,
+    /// Latest system settings, to report their changes.
+    pub(super) system_settings: Cell<SystemSettings>,
 }
 
 impl EventProcessor {
@@ -140,7 +158,9 @@ impl EventProcessor {
     where
         F: FnMut(Event<T>),
     {
-        let wt = get_xtarget(&self.target);
+        // The target is cloned so that the key events can be processed by `&mut self` methods.
+        let target = Rc::clone(&self.target);
+        let wt = get_xtarget(&target);
         let atoms = wt.x_connection().atoms();
         // XFilterEvent tells us when an event has been discarded by the input method.
         // Specifically, this involves all of the KeyPress events in compose/pre-edit sequences,
@@ -165,7 +185,21 @@ impl EventProcessor {
                 let window = client_msg.window as xproto::Window;
                 let window_id = mkwid(window);
 
-                if client_msg.data.get_long(0) as xproto::Atom == wt.wm_delete_window {
+                if window == wt.root && client_msg.message_type == atoms[Manager] as c_ulong {
+                    // A new XSETTINGS manager could have taken over, the other selections are
+                    // announced the same way.
+                    let selection = client_msg.data.get_long(1) as xproto::Atom;
+                    match wt.xconn.xsettings_selection() {
+                        Ok(xsettings_selection) if selection == xsettings_selection => {
+                            if let Err(err) = wt.xconn.update_xsettings_owner() {
+                                warn!("Failed to find the XSETTINGS manager: {err}");
+                            }
+                            self.process_xsettings_change(&mut callback);
+                        }
+                        Ok(_) => (),
+                        Err(err) => warn!("Failed to find the XSETTINGS selection: {err}"),
+                    }
+                } else if client_msg.data.get_long(0) as xproto::Atom == wt.wm_delete_window {
                     callback(Event::WindowEvent {
                         window_id,
                         event: WindowEvent::CloseRequested,
@@ -195,6 +229,22 @@ impl EventProcessor {
                             client_msg.serialize(),
                         )
                         .expect_then_ignore_error("Failed to send `ClientMessage` event.");
+                } else if client_msg.data.get_long(0) as xproto::Atom == atoms[_NET_WM_SYNC_REQUEST]
+                {
+                    // The 64-bit value is split into the low and high 32 bits.
+                    let value = (client_msg.data.get_long(2) as u32 as i64)
+                        | ((client_msg.data.get_long(3) as i32 as i64) << 32);
+                    let extended = client_msg.data.get_long(4) != 0;
+                    self.with_window(window, |window| window.handle_sync_request(value, extended));
+                } else if client_msg.message_type == atoms[_NET_WM_FRAME_DRAWN] as c_ulong {
+                    // The counter value and the time in microseconds are split like above.
+                    let value = (client_msg.data.get_long(0) as u32 as i64)
+                        | ((client_msg.data.get_long(1) as i32 as i64) << 32);
+                    let time = (client_msg.data.get_long(2) as u32 as u64)
+                        | ((client_msg.data.get_long(3) as u32 as u64) << 32);
+                    self.with_window(window, |window| {
+                        window.frame_drawn(value, Duration::from_micros(time))
+                    });
                 } else if client_msg.message_type == atoms[XdndEnter] as c_ulong {
                     let source_window = client_msg.data.get_long(0) as xproto::Window;
                     let flags = client_msg.data.get_long(1);
@@ -514,7 +564,14 @@ impl EventProcessor {
                             window_id,
                             event: WindowEvent::Resized(new_inner_size.into()),
                         });
+
+                        // Ensure that the window is redrawn with the new size.
+                        if window.sync_counter().is_some() {
+                            window.request_redraw();
+                        }
                     }
+
+                    window.handle_sync_configure(resized);
                 }
             }
 
@@ -578,6 +635,10 @@ impl EventProcessor {
 
                 if atom == xproto::Atom::from(xproto::AtomEnum::RESOURCE_MANAGER) {
                     self.process_dpi_change(&mut callback);
+                } else if atom == atoms[_XSETTINGS_SETTINGS]
+                    && Some(xev.window as xproto::Window) == wt.xconn.xsettings_owner()
+                {
+                    self.process_xsettings_change(&mut callback);
                 }
             }
 
@@ -604,92 +665,13 @@ impl EventProcessor {
 
                     callback(Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::RedrawRequested,
+                        event: WindowEvent::RedrawRequested { frame_time: None },
                     });
                 }
             }
 
             // Note that in compose/pre-edit sequences, we'll always receive KeyRelease events
-            ty @ ffi::KeyPress | ty @ ffi::KeyRelease => {
-                let xkev: &mut ffi::XKeyEvent = xev.as_mut();
-
-                // Set the timestamp.
-                wt.xconn.set_timestamp(xkev.time as xproto::Timestamp);
-
-                let window = match self.active_window {
-                    Some(window) => window,
-                    None => return,
-                };
-
-                let window_id = mkwid(window);
-                let device_id = mkdid(util::VIRTUAL_CORE_KEYBOARD);
-
-                let keycode = xkev.keycode as _;
-
-                // Update state to track key repeats and determine whether this key was a repeat.
-                //
-                // Note, when a key is held before focusing on this window the first
-                // (non-synthetic) event will not be flagged as a repeat (also note that the
-                // synthetic press event that is generated before this when the window gains focus
-                // will also not be flagged as a repeat).
-                //
-                // Only keys that can repeat should change the held_key_press state since a
-                // continuously held repeatable key may continue repeating after the press of a
-                // non-repeatable key.
-                let repeat = if self.kb_state.key_repeats(keycode) {
-                    let is_latest_held = self.held_key_press == Some(keycode);
-
-                    if ty == ffi::KeyPress {
-                        self.held_key_press = Some(keycode);
-                        is_latest_held
-                    } else {
-                        // Check that the released key is the latest repeatable key that has been
-                        // pressed, since repeats will continue for the latest key press if a
-                        // different previously pressed key is released.
-                        if is_latest_held {
-                            self.held_key_press = None;
-                        }
-                        false
-                    }
-                } else {
-                    false
-                };
-
-                let state = if ty == ffi::KeyPress {
-                    ElementState::Pressed
-                } else {
-                    ElementState::Released
-                };
-
-                if keycode != 0 && !self.is_composing {
-                    let event = self.kb_state.process_key_event(keycode, state, repeat);
-                    callback(Event::WindowEvent {
-                        window_id,
-                        event: WindowEvent::KeyboardInput {
-                            device_id,
-                            event,
-                            is_synthetic: false,
-                        },
-                    });
-                } else if let Some(ic) = wt.ime.borrow().get_context(window as ffi::Window) {
-                    let written = wt.xconn.lookup_utf8(ic, xkev);
-                    if !written.is_empty() {
-                        let event = Event::WindowEvent {
-                            window_id,
-                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
-                        };
-                        callback(event);
-
-                        let event = Event::WindowEvent {
-                            window_id,
-                            event: WindowEvent::Ime(Ime::Commit(written)),
-                        };
-
-                        self.is_composing = false;
-                        callback(event);
-                    }
-                }
-            }
+            ffi::KeyPress | ffi::KeyRelease => self.process_key_event(xev.as_mut(), &mut callback),
 
             ffi::GenericEvent => {
                 let guard = if let Some(e) = GenericEventCookie::from_event(&wt.xconn, *xev) {
@@ -732,6 +714,25 @@ impl EventProcessor {
                         } else {
                             Released
                         };
+
+                        // Dismiss the popup holding the grab on clicks outside of it.
+                        if state == Pressed {
+                            let outside = self.with_window(xev.event as xproto::Window, |window| {
+                                let (width, height) = window.inner_size_physical();
+                                window.is_popup_grab()
+                                    && (xev.event_x < 0.
+                                        || xev.event_y < 0.
+                                        || xev.event_x >= width as f64
+                                        || xev.event_y >= height as f64)
+                            });
+                            if outside == Some(true) {
+                                callback(Event::WindowEvent {
+                                    window_id,
+                                    event: WindowEvent::PopupDone,
+                                });
+                                return;
+                            }
+                        }
                         match xev.detail as u32 {
                             ffi::Button1 => callback(Event::WindowEvent {
                                 window_id,
@@ -965,6 +966,42 @@ impl EventProcessor {
                             });
                         }
                     }
+                    // Only the input grab of the popups reports the keys with XInput2, they're
+                    // handled like the core key events, including by the input method.
+                    ffi::XI_KeyPress | ffi::XI_KeyRelease => {
+                        let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
+                        let mut key_event = ffi::XEvent {
+                            key: ffi::XKeyEvent {
+                                type_: if xev.evtype == ffi::XI_KeyPress {
+                                    ffi::KeyPress
+                                } else {
+                                    ffi::KeyRelease
+                                },
+                                serial: xev.serial,
+                                send_event: xev.send_event,
+                                display: xev.display,
+                                window: xev.event,
+                                root: xev.root,
+                                subwindow: xev.child,
+                                time: xev.time,
+                                x: xev.event_x as c_int,
+                                y: xev.event_y as c_int,
+                                x_root: xev.root_x as c_int,
+                                y_root: xev.root_y as c_int,
+                                state: xev.mods.effective as c_uint
+                                    | (xev.group.effective as c_uint) << 13,
+                                keycode: xev.detail as c_uint,
+                                same_screen: ffi::True,
+                            },
+                        };
+
+                        let filtered = unsafe {
+                            (wt.xconn.xlib.XFilterEvent)(&mut key_event, key_event.key.window)
+                        };
+                        if filtered != ffi::True {
+                            self.process_key_event(key_event.as_mut(), &mut callback);
+                        }
+                    }
                     ffi::XI_FocusIn => {
                         let xev: &ffi::XIFocusInEvent = unsafe { &*(xev.data as *const _) };
                         let window = xev.event as xproto::Window;
@@ -994,9 +1031,7 @@ impl EventProcessor {
                                 event: Focused(true),
                             });
 
-                            let modifiers: crate::keyboard::ModifiersState =
-                                self.kb_state.mods_state().into();
-                            self.send_modifiers(modifiers, &mut callback);
+                            self.send_modifiers(self.current_modifiers(), &mut callback);
 
                             // The deviceid for this event is for a keyboard instead of a pointer,
                             // so we have to do a little extra work.
@@ -1058,7 +1093,7 @@ impl EventProcessor {
                             // window regains focus.
                             self.held_key_press = None;
 
-                            self.send_modifiers(ModifiersState::empty(), &mut callback);
+                            self.send_modifiers(Modifiers::default(), &mut callback);
 
                             if let Some(window) = self.with_window(window, Arc::clone) {
                                 window.shared_state_lock().has_focus = false;
@@ -1272,13 +1307,17 @@ impl EventProcessor {
                                 && (keycodes_changed || geometry_changed)
                             {
                                 unsafe { self.kb_state.init_with_x11_keymap() };
-                                let modifiers = self.kb_state.mods_state();
-                                self.send_modifiers(modifiers.into(), &mut callback);
+                                self.send_modifiers(self.current_modifiers(), &mut callback);
+                                self.process_keyboard_layout_change(true, &mut callback);
                             }
                         }
+                        ffi::XkbControlsNotify => {
+                            self.process_system_settings_change(&mut callback);
+                        }
                         ffi::XkbMapNotify => {
                             unsafe { self.kb_state.init_with_x11_keymap() };
-                            self.send_modifiers(self.kb_state.mods_state().into(), &mut callback);
+                            self.send_modifiers(self.current_modifiers(), &mut callback);
+                            self.process_keyboard_layout_change(true, &mut callback);
                         }
                         ffi::XkbStateNotify => {
                             let xev =
@@ -1299,7 +1338,12 @@ impl EventProcessor {
                                 xev.locked_group as u32,
                             );
 
-                            self.send_modifiers(self.kb_state.mods_state().into(), &mut callback);
+                            self.send_modifiers(self.current_modifiers(), &mut callback);
+                            self.process_keyboard_layout_change(false, &mut callback);
+                        }
+                        ffi::XkbIndicatorStateNotify => {
+                            self.scroll_lock = wt.xconn.scroll_lock();
+                            self.send_modifiers(self.current_modifiers(), &mut callback);
                         }
                         _ => {}
                     }
@@ -1337,14 +1381,18 @@ impl EventProcessor {
                     self.is_composing = true;
                     callback(Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::Ime(Ime::Preedit("".to_owned(), None)),
+                        event: WindowEvent::Ime(Ime::Preedit("".to_owned(), None, Vec::new())),
                     });
                 }
-                ImeEvent::Update(text, position) => {
+                ImeEvent::Update(text, position, styles) => {
                     if self.is_composing {
                         callback(Event::WindowEvent {
                             window_id,
-                            event: WindowEvent::Ime(Ime::Preedit(text, Some((position, position)))),
+                            event: WindowEvent::Ime(Ime::Preedit(
+                                text,
+                                Some((position, position)),
+                                styles,
+                            )),
                         });
                     }
                 }
@@ -1353,7 +1401,7 @@ impl EventProcessor {
                     // Issue empty preedit on `Done`.
                     callback(Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     });
                 }
                 ImeEvent::Disabled => {
@@ -1367,12 +1415,155 @@ impl EventProcessor {
         }
     }
 
+    /// Process a core key event, from the core input or converted from an XInput2 one.
+    fn process_key_event<T: 'static, F>(&mut self, xkev: &mut ffi::XKeyEvent, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        let ty = xkev.type_;
+
+        // Set the timestamp.
+        wt.xconn.set_timestamp(xkev.time as xproto::Timestamp);
+
+        // The global hotkeys are grabbed on the root window.
+        if xkev.window as xproto::Window == wt.root {
+            let keycode = xkev.keycode as xproto::Keycode;
+            if ty == ffi::KeyRelease {
+                // Without the detectable auto repeat, the repeats are a release followed by a
+                // press at the same time, the key is still held.
+                if self.held_hotkey == Some(keycode) && !self.is_repeat_release(xkev) {
+                    self.held_hotkey = None;
+                }
+            } else if self.held_hotkey.replace(keycode) != Some(keycode) {
+                if let Some(id) = wt.hotkey(keycode, xkev.state) {
+                    callback(Event::DeviceEvent {
+                        device_id: mkdid(util::VIRTUAL_CORE_KEYBOARD),
+                        event: DeviceEvent::GlobalHotkey(id),
+                    });
+                }
+            }
+            return;
+        }
+
+        let window = match self.active_window {
+            Some(window) => window,
+            None => return,
+        };
+
+        let window_id = mkwid(window);
+        let device_id = mkdid(util::VIRTUAL_CORE_KEYBOARD);
+
+        let keycode = xkev.keycode as _;
+
+        // Update state to track key repeats and determine whether this key was a repeat.
+        //
+        // Note, when a key is held before focusing on this window the first
+        // (non-synthetic) event will not be flagged as a repeat (also note that the
+        // synthetic press event that is generated before this when the window gains focus
+        // will also not be flagged as a repeat).
+        //
+        // Only keys that can repeat should change the held_key_press state since a
+        // continuously held repeatable key may continue repeating after the press of a
+        // non-repeatable key.
+        let repeat = if self.kb_state.key_repeats(keycode) {
+            let is_latest_held = self.held_key_press == Some(keycode);
+
+            if ty == ffi::KeyPress {
+                self.held_key_press = Some(keycode);
+                is_latest_held
+            } else {
+                // Check that the released key is the latest repeatable key that has been
+                // pressed, since repeats will continue for the latest key press if a
+                // different previously pressed key is released.
+                if is_latest_held {
+                    self.held_key_press = None;
+                }
+                false
+            }
+        } else {
+            false
+        };
+
+        let state = if ty == ffi::KeyPress {
+            ElementState::Pressed
+        } else {
+            ElementState::Released
+        };
+
+        if keycode != 0 && !self.is_composing {
+            let event = self.kb_state.process_key_event(keycode, state, repeat);
+            callback(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::KeyboardInput {
+                    device_id,
+                    event,
+                    is_synthetic: false,
+                },
+            });
+
+            if let Some((pending, status)) = self.kb_state.take_compose_update() {
+                callback(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::ComposeState { pending, status },
+                });
+            }
+        } else if let Some(ic) = wt.ime.borrow().get_context(window as ffi::Window) {
+            let written = wt.xconn.lookup_utf8(ic, xkev);
+            if !written.is_empty() {
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
+                };
+                callback(event);
+
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::Ime(Ime::Commit(written)),
+                };
+
+                self.is_composing = false;
+                callback(event);
+            }
+        }
+    }
+
+    /// Whether the key release is followed by a press of the same key at the same time, which is
+    /// how the auto repeat is reported when it isn't detectable.
+    fn is_repeat_release(&self, xkev: &ffi::XKeyEvent) -> bool {
+        let wt = get_xtarget(&self.target);
+        unsafe {
+            // Don't block when the press isn't read yet.
+            if (wt.xconn.xlib.XEventsQueued)(wt.xconn.display, QUEUED_AFTER_READING) == 0 {
+                return false;
+            }
+
+            let mut next_event = MaybeUninit::<ffi::XEvent>::uninit();
+            (wt.xconn.xlib.XPeekEvent)(wt.xconn.display, next_event.as_mut_ptr());
+            let next_event = next_event.assume_init();
+
+            next_event.get_type() == ffi::KeyPress
+                && next_event.key.window == xkev.window
+                && next_event.key.keycode == xkev.keycode
+                && next_event.key.time == xkev.time
+        }
+    }
+
+    /// The modifiers and the lock keys of the keyboard.
+    fn current_modifiers(&self) -> Modifiers {
+        let mut modifiers: Modifiers = self.kb_state.mods_state().into();
+        modifiers
+            .lock_state
+            .set(LockState::SCROLL_LOCK, self.scroll_lock);
+        modifiers
+    }
+
     /// Send modifiers for the active window.
     ///
     /// The event won't be send when the `modifiers` match the previosly `sent` modifiers value.
     fn send_modifiers<T: 'static, F: FnMut(Event<T>)>(
         &self,
-        modifiers: ModifiersState,
+        modifiers: Modifiers,
         callback: &mut F,
     ) {
         let window_id = match self.active_window {
@@ -1383,7 +1574,7 @@ impl EventProcessor {
         if self.modifiers.replace(modifiers) != modifiers {
             callback(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::ModifiersChanged(self.modifiers.get().into()),
+                event: WindowEvent::ModifiersChanged(self.modifiers.get()),
             });
         }
     }
@@ -1419,6 +1610,132 @@ impl EventProcessor {
         }
     }
 
+    fn process_xsettings_change<T: 'static, F>(&self, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        let prev_theme = wt.xconn.system_theme();
+        let prev_settings = wt.xconn.reload_xsettings();
+        let theme = wt
+            .xconn
+            .system_theme()
+            .filter(|&theme| Some(theme) != prev_theme);
+        let accent_color = wt.xconn.xsettings().accent_color();
+        let accent_color =
+            accent_color.filter(|&accent_color| Some(accent_color) != prev_settings.accent_color());
+
+        let cursor_theme = wt.xconn.xsettings().cursor_theme_name().map(str::to_owned);
+        let cursor_theme_changed = cursor_theme.as_deref() != prev_settings.cursor_theme_name();
+        if let Some(cursor_theme) = cursor_theme.filter(|_| cursor_theme_changed) {
+            wt.xconn.set_cursor_theme(&cursor_theme);
+        }
+
+        for window in wt.windows.borrow().values().filter_map(|w| w.upgrade()) {
+            if cursor_theme_changed {
+                window.reload_cursor_icon();
+            }
+
+            if let Some(accent_color) = accent_color {
+                callback(Event::WindowEvent {
+                    window_id: crate::window::WindowId(window.id()),
+                    event: WindowEvent::AccentColorChanged(accent_color),
+                });
+            }
+        }
+
+        if let Some(theme) = theme {
+            self.process_theme_change(theme, callback);
+        }
+
+        // The scale factor of the monitors is computed from the settings.
+        if wt.xconn.xsettings().scale_factor() != prev_settings.scale_factor() {
+            self.process_dpi_change(callback);
+        }
+
+        self.process_system_settings_change(callback);
+    }
+
+    /// Update the theme of the `color-scheme` of the XDG desktop portal.
+    pub(super) fn process_color_scheme_change<T: 'static, F>(
+        &self,
+        color_scheme: Option<Theme>,
+        callback: &mut F,
+    ) where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        let prev_theme = wt.xconn.system_theme();
+        *wt.xconn.color_scheme.lock().unwrap() = color_scheme;
+        let theme = wt.xconn.system_theme();
+        if let Some(theme) = theme.filter(|&theme| Some(theme) != prev_theme) {
+            self.process_theme_change(theme, callback);
+        }
+    }
+
+    /// Report the change of the system theme to the windows following it.
+    fn process_theme_change<T: 'static, F>(&self, theme: Theme, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        for window in wt.windows.borrow().values().filter_map(|w| w.upgrade()) {
+            // Only the windows following the system theme are affected.
+            if window.shared_state_lock().theme.is_some() {
+                continue;
+            }
+
+            window
+                .set_theme_inner(None)
+                .expect_then_ignore_error("Failed to change window theme");
+            callback(Event::WindowEvent {
+                window_id: crate::window::WindowId(window.id()),
+                event: WindowEvent::ThemeChanged(theme),
+            });
+        }
+    }
+
+    fn process_system_settings_change<T: 'static, F>(&self, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        let settings = wt.xconn.system_settings();
+        if self.system_settings.replace(settings) != settings {
+            callback(Event::SystemSettingsChanged(settings));
+        }
+    }
+
+    /// Report the change of the keyboard layouts, re-reading their names when the keymap changed.
+    fn process_keyboard_layout_change<T: 'static, F>(&self, keymap_changed: bool, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        let mut keyboard_layouts = wt.keyboard_layouts.borrow_mut();
+        let info = if keymap_changed {
+            wt.xconn.keyboard_layout_info(&self.kb_state)
+        } else {
+            KeyboardLayoutInfo {
+                active: self.kb_state.active_layout(),
+                ..keyboard_layouts.clone()
+            }
+        };
+
+        if *keyboard_layouts == info {
+            return;
+        }
+        *keyboard_layouts = info.clone();
+        drop(keyboard_layouts);
+
+        if let Some(window) = self.active_window {
+            callback(Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::KeyboardLayoutChanged(info),
+            });
+        }
+    }
+
     fn process_dpi_change<T: 'static, F>(&self, callback: &mut F)
     where
         F: FnMut(Event<T>),
@@ -1428,7 +1745,6 @@ impl EventProcessor {
             .reload_database()
             .expect("failed to reload Xft database");
 
-        // In the future, it would be quite easy to emit monitor hotplug events.
         let prev_list = {
             let prev_list = wt.xconn.invalidate_cached_monitor_list();
             match prev_list {
@@ -1441,6 +1757,32 @@ impl EventProcessor {
             .xconn
             .available_monitors()
             .expect("Failed to get monitor list");
+
+        // The CRTCs could be reassigned, so match the monitors by their names.
+        let wrap = |monitor: &MonitorHandle| RootMonitorHandle {
+            inner: PlatformMonitorHandle::X(monitor.clone()),
+        };
+        for prev_monitor in &prev_list {
+            if !new_list
+                .iter()
+                .any(|new_monitor| new_monitor.name == prev_monitor.name)
+            {
+                callback(Event::MonitorRemoved(wrap(prev_monitor)));
+            }
+        }
+        for new_monitor in &new_list {
+            match prev_list
+                .iter()
+                .find(|prev_monitor| prev_monitor.name == new_monitor.name)
+            {
+                Some(prev_monitor) if new_monitor.is_reconfigured(prev_monitor) => {
+                    callback(Event::MonitorChanged(wrap(new_monitor)))
+                }
+                Some(_) => (),
+                None => callback(Event::MonitorAdded(wrap(new_monitor))),
+            }
+        }
+
         for new_monitor in new_list {
             // Previous list may be empty, in case of disconnecting and
             // reconnecting the only one monitor. We still need to emit events in
@@ -1,10 +1,12 @@
 use std::ffi::CStr;
+use std::ops::Range;
 use std::os::raw::c_short;
 use std::sync::Arc;
-use std::{mem, ptr};
+use std::{mem, ptr, slice};
 
 use x11_dl::xlib::{XIMCallback, XIMPreeditCaretCallbackStruct, XIMPreeditDrawCallbackStruct};
 
+use crate::event::PreeditStyle;
 use crate::platform_impl::platform::x11::ime::input_method::{Style, XIMStyle};
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventSender};
 
@@ -20,6 +22,11 @@ pub enum ImeContextCreationError {
     Null,
 }
 
+/// The feedbacks of the preedit text, from `Xlib.h`.
+const XIM_REVERSE: ffi::XIMFeedback = 1;
+const XIM_UNDERLINE: ffi::XIMFeedback = 1 << 1;
+const XIM_HIGHLIGHT: ffi::XIMFeedback = 1 << 2;
+
 /// The callback used by XIM preedit functions.
 type XIMProcNonnull = unsafe extern "C" fn(ffi::XIM, ffi::XPointer, ffi::XPointer);
 
@@ -41,6 +48,7 @@ extern "C" fn preedit_start_callback(
     let client_data = unsafe { &mut *(client_data as *mut ImeContextClientData) };
 
     client_data.text.clear();
+    client_data.feedbacks.clear();
     client_data.cursor_pos = 0;
     client_data
         .event_sender
@@ -59,6 +67,7 @@ extern "C" fn preedit_done_callback(
 
     // Drop text buffer and reset cursor position on done.
     client_data.text = Vec::new();
+    client_data.feedbacks = Vec::new();
     client_data.cursor_pos = 0;
 
     client_data
@@ -73,6 +82,37 @@ fn calc_byte_position(text: &[char], pos: usize) -> usize {
         .fold(0, |byte_pos, text| byte_pos + text.len_utf8())
 }
 
+/// Group the characters with the same feedback into the styled segments of the preedit.
+fn calc_preedit_styles(
+    text: &[char],
+    feedbacks: &[ffi::XIMFeedback],
+) -> Vec<(Range<usize>, PreeditStyle)> {
+    let mut styles: Vec<(Range<usize>, PreeditStyle)> = Vec::new();
+    let mut byte_pos = 0;
+    for (char, &feedback) in text.iter().zip(feedbacks) {
+        let start = byte_pos;
+        byte_pos += char.len_utf8();
+
+        let style = PreeditStyle {
+            underline: feedback & XIM_UNDERLINE != 0,
+            highlight: feedback & XIM_HIGHLIGHT != 0,
+            selected: feedback & XIM_REVERSE != 0,
+        };
+        if style == PreeditStyle::default() {
+            continue;
+        }
+
+        match styles.last_mut() {
+            Some((range, last_style)) if range.end == start && *last_style == style => {
+                range.end = byte_pos;
+            }
+            _ => styles.push((start..byte_pos, style)),
+        }
+    }
+
+    styles
+}
+
 /// Preedit text information to be drawn inline by the client.
 extern "C" fn preedit_draw_callback(
     _xim: ffi::XIM,
@@ -96,39 +136,52 @@ extern "C" fn preedit_draw_callback(
     }
 
     // NULL indicate text deletion
-    let mut new_chars = if call_data.text.is_null() {
-        Vec::new()
+    let (mut new_chars, mut new_feedbacks) = if call_data.text.is_null() {
+        (Vec::new(), Vec::new())
     } else {
         let xim_text = unsafe { &mut *(call_data.text) };
         if xim_text.encoding_is_wchar > 0 {
             return;
         }
 
+        let mut feedbacks = if xim_text.feedback.is_null() {
+            vec![0; xim_text.length as usize]
+        } else {
+            unsafe { slice::from_raw_parts(xim_text.feedback, xim_text.length as usize) }.to_vec()
+        };
+
         let new_text = unsafe { xim_text.string.multi_byte };
 
+        // NULL string indicate that only the feedbacks of the changed text were updated.
         if new_text.is_null() {
+            let start = chg_range.start;
+            for (old, new) in client_data.feedbacks[start..].iter_mut().zip(feedbacks) {
+                *old = new;
+            }
+            client_data.send_update();
             return;
         }
 
         let new_text = unsafe { CStr::from_ptr(new_text) };
 
-        String::from(new_text.to_str().expect("Invalid UTF-8 String from IME"))
-            .chars()
-            .collect()
+        let chars: Vec<char> =
+            String::from(new_text.to_str().expect("Invalid UTF-8 String from IME"))
+                .chars()
+                .collect();
+        feedbacks.resize(chars.len(), 0);
+        (chars, feedbacks)
     };
     let mut old_text_tail = client_data.text.split_off(chg_range.end);
     client_data.text.truncate(chg_range.start);
     client_data.text.append(&mut new_chars);
     client_data.text.append(&mut old_text_tail);
-    let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
 
-    client_data
-        .event_sender
-        .send((
-            client_data.window,
-            ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
-        ))
-        .expect("failed to send preedit update event");
+    let mut old_feedbacks_tail = client_data.feedbacks.split_off(chg_range.end);
+    client_data.feedbacks.truncate(chg_range.start);
+    client_data.feedbacks.append(&mut new_feedbacks);
+    client_data.feedbacks.append(&mut old_feedbacks_tail);
+
+    client_data.send_update();
 }
 
 /// Handling of cursor movements in preedit text.
@@ -142,15 +195,7 @@ extern "C" fn preedit_caret_callback(
 
     if call_data.direction == ffi::XIMCaretDirection::XIMAbsolutePosition {
         client_data.cursor_pos = call_data.position as usize;
-        let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
-
-        client_data
-            .event_sender
-            .send((
-                client_data.window,
-                ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
-            ))
-            .expect("failed to send preedit update event");
+        client_data.send_update();
     }
 }
 
@@ -184,9 +229,25 @@ struct ImeContextClientData {
     window: ffi::Window,
     event_sender: ImeEventSender,
     text: Vec<char>,
+    feedbacks: Vec<ffi::XIMFeedback>,
     cursor_pos: usize,
 }
 
+impl ImeContextClientData {
+    /// Send the current preedit.
+    fn send_update(&self) {
+        let cursor_byte_pos = calc_byte_position(&self.text, self.cursor_pos);
+        let styles = calc_preedit_styles(&self.text, &self.feedbacks);
+
+        self.event_sender
+            .send((
+                self.window,
+                ImeEvent::Update(self.text.iter().collect(), cursor_byte_pos, styles),
+            ))
+            .expect("failed to send preedit update event");
+    }
+}
+
 // XXX: this struct doesn't destroy its XIC resource when dropped.
 // This is intentional, as it doesn't have enough information to know whether or not the context
 // still exists on the server. Since `ImeInner` has that awareness, destruction must be handled
@@ -213,6 +274,7 @@ impl ImeContext {
             window,
             event_sender,
             text: Vec::new(),
+            feedbacks: Vec::new(),
             cursor_pos: 0,
         }));
 
@@ -383,3 +445,37 @@ impl ImeContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preedit_styles() {
+        let text: Vec<char> = "日本語をにゅうりょく".chars().collect();
+        let feedbacks = [
+            XIM_REVERSE,
+            XIM_REVERSE,
+            XIM_REVERSE,
+            XIM_REVERSE,
+            XIM_UNDERLINE,
+            XIM_UNDERLINE,
+            XIM_UNDERLINE,
+            XIM_UNDERLINE,
+            0,
+            0,
+        ];
+        let underline = PreeditStyle {
+            underline: true,
+            ..Default::default()
+        };
+        let selected = PreeditStyle {
+            selected: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            calc_preedit_styles(&text, &feedbacks),
+            vec![(0..12, selected), (12..24, underline)]
+        );
+    }
+}
@@ -5,6 +5,7 @@ mod context;
 mod inner;
 mod input_method;
 
+use std::ops::Range;
 use std::sync::{
     mpsc::{Receiver, Sender},
     Arc,
@@ -15,6 +16,7 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 
 use super::{ffi, util, XConnection, XError};
+use crate::event::PreeditStyle;
 
 pub use self::context::ImeContextCreationError;
 use self::{
@@ -29,7 +31,7 @@ use self::{
 pub enum ImeEvent {
     Enabled,
     Start,
-    Update(String, usize),
+    Update(String, usize, Vec<(Range<usize>, PreeditStyle)>),
     End,
     Disabled,
 }
@@ -50,6 +50,7 @@ use x11rb::x11_utils::X11Error as LogicalError;
 use x11rb::{
     connection::RequestConnection,
     protocol::{
+        sync::ConnectionExt as _,
         xinput::{self, ConnectionExt as _},
         xkb,
         xproto::{self, ConnectionExt as _},
@@ -63,15 +64,23 @@ use x11rb::{
 pub(super) use self::util::CustomCursor;
 use self::{
     dnd::{Dnd, DndState},
-    event_processor::EventProcessor,
+    event_processor::{EventProcessor, KEYCODE_OFFSET},
     ime::{Ime, ImeCreationError, ImeReceiver, ImeRequest, ImeSender},
 };
-use super::{common::xkb_state::KbdState, ControlFlow, OsError};
+use super::{
+    common::{
+        keymap,
+        portal::{self, Appearance},
+        xkb_state::KbdState,
+    },
+    ControlFlow, OsError,
+};
 use crate::{
-    error::{EventLoopError, OsError as RootOsError},
-    event::{Event, StartCause, WindowEvent},
+    error::{EventLoopError, ExternalError, OsError as RootOsError},
+    event::{Event, HotkeyId, StartCause, WindowEvent},
     event_loop::{DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW},
-    platform::pump_events::PumpStatus,
+    keyboard::{KeyboardLayoutInfo, ModifiersState, PhysicalKey},
+    platform::{pump_events::PumpStatus, xkb::KeymapSource},
     platform_impl::platform::{min_timeout, WindowId},
     window::WindowAttributes,
 };
@@ -154,6 +163,10 @@ pub struct EventLoopWindowTarget {
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationToken>,
     device_events: Cell<DeviceEvents>,
+    keyboard_layouts: RefCell<KeyboardLayoutInfo>,
+    /// The registered global hotkeys, with their keycode and their core modifiers.
+    hotkeys: RefCell<HashMap<HotkeyId, (xproto::Keycode, u16)>>,
+    next_hotkey_id: Cell<u32>,
 }
 
 pub struct EventLoop<T: 'static> {
@@ -164,6 +177,7 @@ pub struct EventLoop<T: 'static> {
     redraw_receiver: PeekableReceiver<WindowId>,
     user_receiver: PeekableReceiver<T>,
     activation_receiver: PeekableReceiver<ActivationToken>,
+    appearance_receiver: PeekableReceiver<Appearance>,
     user_sender: Sender<T>,
     target: Rc<RootELW>,
 
@@ -171,6 +185,14 @@ pub struct EventLoop<T: 'static> {
     state: EventLoopState,
 }
 
+impl<T: 'static> Drop for EventLoop<T> {
+    fn drop(&mut self) {
+        // Don't leave the gamma ramps changed by the application, the connection is shared by
+        // the event loops and is never closed.
+        get_xtarget(&self.target).xconn.restore_gamma_ramps();
+    }
+}
+
 type ActivationToken = (WindowId, crate::event_loop::AsyncRequestSerial);
 
 struct EventLoopState {
@@ -191,7 +213,11 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
 }
 
 impl<T: 'static> EventLoop<T> {
-    pub(crate) fn new(xconn: Arc<XConnection>) -> EventLoop<T> {
+    pub(crate) fn new(
+        xconn: Arc<XConnection>,
+        xkb_keymap: Option<KeymapSource>,
+        xkb_compose: bool,
+    ) -> EventLoop<T> {
         let root = xconn.default_root().root;
         let atoms = xconn.atoms();
 
@@ -238,6 +264,29 @@ impl<T: 'static> EventLoop<T> {
             .select_xrandr_input(root)
             .expect("Failed to query XRandR extension");
 
+        // Watch for the new XSETTINGS managers, announced with the `MANAGER` client message on
+        // the root window, and for the changes of the `RESOURCE_MANAGER` with `Xft.dpi`.
+        xconn
+            .xcb_connection()
+            .change_window_attributes(
+                root,
+                &xproto::ChangeWindowAttributesAux::new().event_mask(
+                    xproto::EventMask::STRUCTURE_NOTIFY | xproto::EventMask::PROPERTY_CHANGE,
+                ),
+            )
+            .expect_then_ignore_error("Failed to select events on the root window");
+        if let Err(err) = xconn.update_xsettings_owner() {
+            warn!("Failed to find the XSETTINGS manager: {err}");
+        }
+        xconn.reload_xsettings();
+        if let Some(theme) = xconn.xsettings().cursor_theme_name() {
+            xconn.set_cursor_theme(theme);
+        }
+
+        // Cache the monitor list to compare it against on the XRandR events. The scale factor
+        // of the monitors depends on the XSETTINGS.
+        let _ = xconn.available_monitors();
+
         let xi2ext = xconn
             .xcb_connection()
             .extension_information(xinput::X11_EXTENSION_NAME)
@@ -296,8 +345,26 @@ impl<T: 'static> EventLoop<T> {
         // Create a channel for sending user events.
         let (user_sender, user_channel) = mpsc::channel();
 
-        let kb_state =
+        // Create a channel for the appearance settings read from the XDG desktop portal.
+        let (appearance_sender, appearance_channel) = mpsc::channel();
+        let appearance_sender = WakeSender {
+            sender: appearance_sender,
+            waker: waker.clone(),
+        };
+        portal::watch_appearance(move |appearance| appearance_sender.send(appearance).is_ok());
+
+        let mut kb_state =
             KbdState::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
+        if let Some(xkb_keymap) = xkb_keymap {
+            kb_state.set_custom_keymap(xkb_keymap);
+        }
+        if !xkb_compose {
+            kb_state.disable_compose();
+        }
+
+        let system_settings = xconn.system_settings();
+        let keyboard_layouts = xconn.keyboard_layout_info(&kb_state);
+        let scroll_lock = xconn.scroll_lock();
 
         let window_target = EventLoopWindowTarget {
             ime,
@@ -318,6 +385,9 @@ impl<T: 'static> EventLoop<T> {
                 waker: waker.clone(),
             },
             device_events: Default::default(),
+            keyboard_layouts: RefCell::new(keyboard_layouts),
+            hotkeys: Default::default(),
+            next_hotkey_id: Cell::new(0),
         };
 
         // Set initial device event filter.
@@ -340,10 +410,13 @@ impl<T: 'static> EventLoop<T> {
             kb_state,
             num_touch: 0,
             held_key_press: None,
+            held_hotkey: None,
             first_touch: None,
             active_window: None,
             modifiers: Default::default(),
+            scroll_lock,
             is_composing: false,
+            system_settings: Cell::new(system_settings),
         };
 
         // Register for device hotplug events
@@ -363,7 +436,9 @@ impl<T: 'static> EventLoop<T> {
                 0x100, // Use the "core keyboard device"
                 xkb::EventType::NEW_KEYBOARD_NOTIFY
                     | xkb::EventType::MAP_NOTIFY
-                    | xkb::EventType::STATE_NOTIFY,
+                    | xkb::EventType::STATE_NOTIFY
+                    | xkb::EventType::CONTROLS_NOTIFY
+                    | xkb::EventType::INDICATOR_STATE_NOTIFY,
             )
             .unwrap();
 
@@ -376,6 +451,7 @@ impl<T: 'static> EventLoop<T> {
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
+            appearance_receiver: PeekableReceiver::from_recv(appearance_channel),
             user_receiver: PeekableReceiver::from_recv(user_channel),
             user_sender,
             target,
@@ -463,6 +539,7 @@ impl<T: 'static> EventLoop<T> {
         self.event_processor.poll()
             || self.user_receiver.has_incoming()
             || self.redraw_receiver.has_incoming()
+            || self.appearance_receiver.has_incoming()
     }
 
     pub fn poll_events_with_timeout<F>(&mut self, mut timeout: Option<Duration>, mut callback: F)
@@ -558,6 +635,15 @@ impl<T: 'static> EventLoop<T> {
         // Process all pending events
         self.drain_events(callback);
 
+        // Follow the `color-scheme` of the portal.
+        while let Ok(appearance) = self.appearance_receiver.try_recv() {
+            let target = &self.target;
+            self.event_processor
+                .process_color_scheme_change(appearance.theme, &mut |event| {
+                    callback(event, target)
+                });
+        }
+
         // Empty activation tokens.
         while let Ok((window_id, serial)) = self.activation_receiver.try_recv() {
             let token = self
@@ -600,14 +686,29 @@ impl<T: 'static> EventLoop<T> {
             }
 
             for window_id in windows {
-                let window_id = crate::window::WindowId(window_id);
+                // The redraw is a frame for the compositor.
+                let frame_time = self
+                    .event_processor
+                    .with_window(window_id.0 as xproto::Window, |window| {
+                        window.begin_frame();
+                        window.take_frame_time()
+                    })
+                    .flatten();
+
                 callback(
                     Event::WindowEvent {
-                        window_id,
-                        event: WindowEvent::RedrawRequested,
+                        window_id: crate::window::WindowId(window_id),
+                        event: WindowEvent::RedrawRequested { frame_time },
                     },
                     &self.target,
                 );
+
+                // In case the user didn't call `pre_present_notify`, assume that the window
+                // was drawn during the event.
+                self.event_processor
+                    .with_window(window_id.0 as xproto::Window, |window| {
+                        window.update_sync_counter()
+                    });
             }
         }
 
@@ -630,7 +731,7 @@ impl<T: 'static> EventLoop<T> {
             self.event_processor.process_event(&mut xev, |event| {
                 if let Event::WindowEvent {
                     window_id: crate::window::WindowId(wid),
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { .. },
                 } = event
                 {
                     wt.redraw_sender.send(wid).unwrap();
@@ -693,6 +794,82 @@ impl EventLoopWindowTarget {
         self.xconn.primary_monitor().ok()
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        self.xconn.system_settings()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> KeyboardLayoutInfo {
+        self.keyboard_layouts.borrow().clone()
+    }
+
+    pub fn set_keyboard_layout(&self, index: usize) -> Result<(), ExternalError> {
+        if index >= self.keyboard_layouts.borrow().layouts.len() {
+            return Err(ExternalError::Os(os_error!(OsError::Misc(
+                "the keyboard layout doesn't exist."
+            ))));
+        }
+
+        self.xconn
+            .lock_keyboard_group(index as u8)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(Arc::new(err)))))
+    }
+
+    pub fn register_global_hotkey(
+        &self,
+        modifiers: ModifiersState,
+        key: PhysicalKey,
+    ) -> Result<HotkeyId, ExternalError> {
+        let keycode = match keymap::physicalkey_to_scancode(key) {
+            Some(scancode) => (scancode + KEYCODE_OFFSET as u32) as xproto::Keycode,
+            None => {
+                return Err(ExternalError::Os(os_error!(OsError::Misc(
+                    "the key has no X11 keycode."
+                ))))
+            }
+        };
+        let modifiers = util::hotkey_modifiers(modifiers);
+
+        if self
+            .hotkeys
+            .borrow()
+            .values()
+            .any(|&hotkey| hotkey == (keycode, modifiers))
+        {
+            return Err(ExternalError::Os(os_error!(OsError::Misc(
+                "the hotkey is already registered."
+            ))));
+        }
+
+        self.xconn
+            .grab_hotkey(self.root, keycode, modifiers)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(Arc::new(err)))))?;
+
+        let id = HotkeyId(self.next_hotkey_id.get());
+        self.next_hotkey_id.set(id.0.wrapping_add(1));
+        self.hotkeys.borrow_mut().insert(id, (keycode, modifiers));
+        Ok(id)
+    }
+
+    pub fn unregister_global_hotkey(&self, id: HotkeyId) {
+        if let Some((keycode, modifiers)) = self.hotkeys.borrow_mut().remove(&id) {
+            if let Err(err) = self.xconn.ungrab_hotkey(self.root, keycode, modifiers) {
+                warn!("Failed to ungrab the hotkey: {err}");
+            }
+        }
+    }
+
+    /// The hotkey of a key press on the root window.
+    pub(crate) fn hotkey(&self, keycode: xproto::Keycode, state: c_uint) -> Option<HotkeyId> {
+        let modifiers = util::key_event_hotkey_modifiers(state);
+        self.hotkeys
+            .borrow()
+            .iter()
+            .find(|(_, &hotkey)| hotkey == (keycode, modifiers))
+            .map(|(&id, _)| id)
+    }
+
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         self.device_events.set(allowed);
     }
@@ -855,6 +1032,24 @@ impl Drop for Window {
         let window = self.deref();
         let xconn = &window.xconn;
 
+        if window.shared_state_lock().fullscreen.is_some() {
+            xconn.remove_fullscreen_window(window.id().0 as xproto::Window, None);
+        }
+
+        if window.is_popup_grab() {
+            window.ungrab_popup_input();
+        }
+
+        for counter in window
+            .sync_counter()
+            .into_iter()
+            .chain(window.frame_counter())
+        {
+            if let Ok(c) = xconn.xcb_connection().sync_destroy_counter(counter) {
+                c.ignore_error();
+            }
+        }
+
         if let Ok(c) = xconn
             .xcb_connection()
             .destroy_window(window.id().0 as xproto::Window)
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use super::{util, X11Error, XConnection};
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    monitor::{GammaRamp, MonitorTransform},
     platform_impl::VideoModeHandle as PlatformVideoModeHandle,
 };
 use x11rb::{
@@ -64,6 +67,8 @@ pub struct MonitorHandle {
     position: (i32, i32),
     /// If the monitor is the primary one
     primary: bool,
+    /// The rotation and reflection of the monitor
+    transform: MonitorTransform,
     /// The refresh rate used by monitor.
     refresh_rate_millihertz: Option<u32>,
     /// The DPI scale factor
@@ -72,6 +77,10 @@ pub struct MonitorHandle {
     pub(crate) rect: util::AaRect,
     /// Supported video modes on this monitor
     video_modes: Vec<VideoModeHandle>,
+    /// The physical size of the monitor in millimeters
+    physical_size_mm: Option<(u32, u32)>,
+    /// The raw EDID of the monitor
+    edid: Option<Arc<[u8]>>,
 }
 
 impl PartialEq for MonitorHandle {
@@ -110,7 +119,50 @@ pub fn mode_refresh_rate_millihertz(mode: &randr::ModeInfo) -> Option<u32> {
     }
 }
 
+/// Convert the rotation of the CRTC, where the reflection along the Y axis is the same as the
+/// reflection along the X axis rotated by 180 degrees.
+fn transform_from_rotation(rotation: randr::Rotation) -> MonitorTransform {
+    use randr::Rotation;
+
+    let has = |flag: Rotation| u16::from(rotation) & u16::from(flag) != 0;
+    let mut degrees = if has(Rotation::ROTATE90) {
+        90
+    } else if has(Rotation::ROTATE180) {
+        180
+    } else if has(Rotation::ROTATE270) {
+        270
+    } else {
+        0
+    };
+    let mut flipped = has(Rotation::REFLECT_X);
+    if has(Rotation::REFLECT_Y) {
+        degrees = (degrees + 180) % 360;
+        flipped = !flipped;
+    }
+
+    match (degrees, flipped) {
+        (90, false) => MonitorTransform::Rotate90,
+        (180, false) => MonitorTransform::Rotate180,
+        (270, false) => MonitorTransform::Rotate270,
+        (0, true) => MonitorTransform::Flipped,
+        (90, true) => MonitorTransform::Flipped90,
+        (180, true) => MonitorTransform::Flipped180,
+        (270, true) => MonitorTransform::Flipped270,
+        _ => MonitorTransform::Normal,
+    }
+}
+
 impl MonitorHandle {
+    /// Whether the video mode, position, or scale factor differ from the `other` monitor.
+    pub(crate) fn is_reconfigured(&self, other: &Self) -> bool {
+        self.dimensions != other.dimensions
+            || self.position != other.position
+            || self.refresh_rate_millihertz != other.refresh_rate_millihertz
+            || self.scale_factor != other.scale_factor
+            || self.primary != other.primary
+            || self.transform != other.transform
+    }
+
     fn new(
         xconn: &XConnection,
         resources: &ScreenResources,
@@ -118,7 +170,13 @@ impl MonitorHandle {
         crtc: &randr::GetCrtcInfoReply,
         primary: bool,
     ) -> Option<Self> {
-        let (name, scale_factor, video_modes) = xconn.get_output_info(resources, crtc)?;
+        let OutputInfo {
+            name,
+            scale_factor,
+            video_modes,
+            physical_size_mm,
+            edid,
+        } = xconn.get_output_info(resources, crtc)?;
         let dimensions = (crtc.width as u32, crtc.height as u32);
         let position = (crtc.x as i32, crtc.y as i32);
 
@@ -131,6 +189,7 @@ impl MonitorHandle {
             .and_then(mode_refresh_rate_millihertz);
 
         let rect = util::AaRect::new(position, dimensions);
+        let transform = transform_from_rotation(crtc.rotation);
 
         Some(MonitorHandle {
             id,
@@ -140,8 +199,11 @@ impl MonitorHandle {
             dimensions,
             position,
             primary,
+            transform,
             rect,
             video_modes,
+            physical_size_mm,
+            edid: edid.map(Into::into),
         })
     }
 
@@ -154,8 +216,11 @@ impl MonitorHandle {
             position: (0, 0),
             refresh_rate_millihertz: None,
             primary: true,
+            transform: MonitorTransform::Normal,
             rect: util::AaRect::new((0, 0), (1, 1)),
             video_modes: Vec::new(),
+            physical_size_mm: None,
+            edid: None,
         }
     }
 
@@ -181,6 +246,21 @@ impl MonitorHandle {
         self.position.into()
     }
 
+    #[inline]
+    pub fn logical_position(&self) -> LogicalPosition<i32> {
+        self.position().to_logical(self.scale_factor)
+    }
+
+    #[inline]
+    pub fn logical_size(&self) -> LogicalSize<u32> {
+        self.size().to_logical(self.scale_factor)
+    }
+
+    #[inline]
+    pub fn transform(&self) -> MonitorTransform {
+        self.transform
+    }
+
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         self.refresh_rate_millihertz
     }
@@ -190,6 +270,40 @@ impl MonitorHandle {
         self.scale_factor
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.physical_size_mm
+    }
+
+    #[inline]
+    pub fn manufacturer(&self) -> Option<String> {
+        self.edid.as_deref().and_then(util::edid_manufacturer)
+    }
+
+    #[inline]
+    pub fn model(&self) -> Option<String> {
+        self.edid.as_deref().and_then(util::edid_model)
+    }
+
+    #[inline]
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        self.edid.as_deref().map(<[u8]>::to_vec)
+    }
+
+    #[inline]
+    pub(crate) fn gamma_ramp(&self, xconn: &XConnection) -> Result<GammaRamp, X11Error> {
+        xconn.get_crtc_gamma(self.id)
+    }
+
+    #[inline]
+    pub(crate) fn set_gamma_ramp(
+        &self,
+        xconn: &XConnection,
+        ramp: &GammaRamp,
+    ) -> Result<(), X11Error> {
+        xconn.set_gamma_ramp(self.id, ramp)
+    }
+
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = PlatformVideoModeHandle> {
         let monitor = self.clone();
@@ -324,6 +438,15 @@ impl XConnection {
     }
 }
 
+/// The information about the RandR output of the monitor.
+pub(crate) struct OutputInfo {
+    pub(crate) name: String,
+    pub(crate) scale_factor: f64,
+    pub(crate) video_modes: Vec<VideoModeHandle>,
+    pub(crate) physical_size_mm: Option<(u32, u32)>,
+    pub(crate) edid: Option<Vec<u8>>,
+}
+
 pub(crate) struct ScreenResources {
     /// List of attached modes.
     modes: Vec<randr::ModeInfo>,
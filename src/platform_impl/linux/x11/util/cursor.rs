@@ -30,6 +30,23 @@ impl XConnection {
             .expect("Failed to set cursor");
     }
 
+    /// Load the named cursors from the given Xcursor theme from now on.
+    pub fn set_cursor_theme(&self, theme: &str) {
+        let theme = match CString::new(theme) {
+            Ok(theme) => theme,
+            Err(_) => return,
+        };
+
+        unsafe { (self.xcursor.XcursorSetTheme)(self.display, theme.as_ptr()) };
+
+        // The server keeps the cursors alive while they're used by the windows.
+        for (_, cursor) in self.cursor_cache.lock().unwrap().drain() {
+            if cursor != 0 {
+                unsafe { (self.xlib.XFreeCursor)(self.display, cursor) };
+            }
+        }
+    }
+
     fn create_empty_cursor(&self) -> ffi::Cursor {
         let data = 0;
         let pixmap = unsafe {
@@ -0,0 +1,83 @@
+//! Parsing of the monitor identification from the EDID.
+
+/// The length of the EDID base block.
+const EDID_BLOCK_LENGTH: usize = 128;
+
+/// The tag of the display descriptor with the monitor name.
+const MONITOR_NAME_TAG: u8 = 0xfc;
+
+/// The three letter PNP ID of the manufacturer.
+pub fn edid_manufacturer(edid: &[u8]) -> Option<String> {
+    if edid.len() < EDID_BLOCK_LENGTH {
+        return None;
+    }
+
+    // Three 5-bit letters, where `1` is `A`.
+    let id = u16::from_be_bytes([edid[8], edid[9]]);
+    [10, 5, 0]
+        .iter()
+        .map(|shift| match (id >> shift) & 0x1f {
+            letter @ 1..=26 => Some((b'A' + letter as u8 - 1) as char),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The monitor name from the display descriptors, or the product code when there's none.
+pub fn edid_model(edid: &[u8]) -> Option<String> {
+    if edid.len() < EDID_BLOCK_LENGTH {
+        return None;
+    }
+
+    let name = edid[54..126].chunks_exact(18).find_map(|descriptor| {
+        if descriptor[..3] != [0, 0, 0] || descriptor[3] != MONITOR_NAME_TAG {
+            return None;
+        }
+
+        // The name is terminated with a line feed and padded with spaces.
+        let text = &descriptor[5..];
+        let text = text.split(|&byte| byte == b'\n').next().unwrap_or(text);
+        let name = String::from_utf8_lossy(text).trim_end().to_owned();
+        (!name.is_empty()).then_some(name)
+    });
+
+    name.or_else(|| {
+        let product_code = u16::from_le_bytes([edid[10], edid[11]]);
+        Some(format!("0x{product_code:04X}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edid(name: Option<&[u8]>) -> Vec<u8> {
+        let mut edid = vec![0; EDID_BLOCK_LENGTH];
+        edid[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+        // `DEL`.
+        edid[8..10].copy_from_slice(&[0x10, 0xac]);
+        edid[10..12].copy_from_slice(&[0x7b, 0xa0]);
+        if let Some(name) = name {
+            edid[72..77].copy_from_slice(&[0, 0, 0, MONITOR_NAME_TAG, 0]);
+            edid[77..77 + name.len()].copy_from_slice(name);
+        }
+        edid
+    }
+
+    #[test]
+    fn manufacturer() {
+        assert_eq!(edid_manufacturer(&edid(None)).as_deref(), Some("DEL"));
+        assert_eq!(edid_manufacturer(&[0; 16]), None);
+    }
+
+    #[test]
+    fn model_from_descriptor() {
+        let edid = edid(Some(b"DELL U2415\n  "));
+        assert_eq!(edid_model(&edid).as_deref(), Some("DELL U2415"));
+    }
+
+    #[test]
+    fn model_from_product_code() {
+        assert_eq!(edid_model(&edid(None)).as_deref(), Some("0xA07B"));
+    }
+}
@@ -0,0 +1,135 @@
+//! Global hotkeys, grabbed on the root window.
+
+use x11rb::connection::Connection;
+
+use super::*;
+use crate::keyboard::ModifiersState;
+
+/// The modifiers of the caps lock and of the num lock, which don't change the hotkeys.
+const LOCK_MASKS: [u16; 4] = [
+    0,
+    ffi::LockMask as u16,
+    ffi::Mod2Mask as u16,
+    (ffi::LockMask | ffi::Mod2Mask) as u16,
+];
+
+/// The modifiers which are compared to the ones of the hotkeys.
+const HOTKEY_MASK: u16 = (ffi::ShiftMask
+    | ffi::ControlMask
+    | ffi::Mod1Mask
+    | ffi::Mod3Mask
+    | ffi::Mod4Mask
+    | ffi::Mod5Mask) as u16;
+
+/// The core modifiers of a hotkey.
+///
+/// The Alt and the Logo modifiers are the `Mod1` and `Mod4` of the usual keymaps, like for the
+/// xkb modifier names.
+pub fn hotkey_modifiers(modifiers: ModifiersState) -> u16 {
+    let mut mask = 0;
+    if modifiers.shift_key() {
+        mask |= ffi::ShiftMask;
+    }
+    if modifiers.control_key() {
+        mask |= ffi::ControlMask;
+    }
+    if modifiers.alt_key() {
+        mask |= ffi::Mod1Mask;
+    }
+    if modifiers.super_key() {
+        mask |= ffi::Mod4Mask;
+    }
+    mask as u16
+}
+
+/// The modifiers of a key event, to be matched against the ones of the hotkeys.
+pub fn key_event_hotkey_modifiers(state: c_uint) -> u16 {
+    state as u16 & HOTKEY_MASK
+}
+
+impl XConnection {
+    /// Grab the key with the modifiers on the window, whatever the state of the lock keys.
+    pub fn grab_hotkey(
+        &self,
+        window: xproto::Window,
+        keycode: xproto::Keycode,
+        modifiers: u16,
+    ) -> Result<(), X11Error> {
+        for (i, lock_mask) in LOCK_MASKS.iter().enumerate() {
+            let result = self
+                .xcb_connection()
+                .grab_key(
+                    false,
+                    window,
+                    xproto::ModMask::from(modifiers | lock_mask),
+                    keycode,
+                    xproto::GrabMode::ASYNC,
+                    xproto::GrabMode::ASYNC,
+                )
+                .map_err(X11Error::from)
+                .and_then(|cookie| cookie.check().map_err(X11Error::from));
+
+            if let Err(err) = result {
+                // Don't keep a part of the grabs.
+                for lock_mask in &LOCK_MASKS[..i] {
+                    let _ = self.xcb_connection().ungrab_key(
+                        keycode,
+                        window,
+                        xproto::ModMask::from(modifiers | lock_mask),
+                    );
+                }
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release a grab of [`XConnection::grab_hotkey`].
+    pub fn ungrab_hotkey(
+        &self,
+        window: xproto::Window,
+        keycode: xproto::Keycode,
+        modifiers: u16,
+    ) -> Result<(), X11Error> {
+        for lock_mask in LOCK_MASKS {
+            self.xcb_connection().ungrab_key(
+                keycode,
+                window,
+                xproto::ModMask::from(modifiers | lock_mask),
+            )?;
+        }
+        self.xcb_connection().flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_mask() {
+        assert_eq!(hotkey_modifiers(ModifiersState::empty()), 0);
+        assert_eq!(
+            hotkey_modifiers(ModifiersState::CONTROL | ModifiersState::SHIFT),
+            (ffi::ControlMask | ffi::ShiftMask) as u16
+        );
+        assert_eq!(
+            hotkey_modifiers(ModifiersState::ALT | ModifiersState::SUPER),
+            (ffi::Mod1Mask | ffi::Mod4Mask) as u16
+        );
+    }
+
+    #[test]
+    fn lock_keys_are_ignored() {
+        let hotkey = hotkey_modifiers(ModifiersState::CONTROL);
+
+        // Caps lock and num lock on, with the first button pressed and the second group active.
+        let state = ffi::ControlMask | ffi::LockMask | ffi::Mod2Mask | ffi::Button1Mask | 1 << 13;
+        assert_eq!(key_event_hotkey_modifiers(state), hotkey);
+
+        let state = ffi::ControlMask | ffi::ShiftMask | ffi::LockMask;
+        assert_ne!(key_event_hotkey_modifiers(state), hotkey);
+    }
+}
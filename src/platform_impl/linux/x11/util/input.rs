@@ -2,6 +2,7 @@ use std::{slice, str};
 use x11rb::protocol::{
     xinput::{self, ConnectionExt as _},
     xkb,
+    xtest::ConnectionExt as _,
 };
 
 use super::*;
@@ -32,6 +33,38 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// Actively grab the device for the window, returning whether the grab succeeded.
+    pub fn grab_device(
+        &self,
+        window: xproto::Window,
+        device_id: u16,
+        mask: xinput::XIEventMask,
+    ) -> Result<bool, X11Error> {
+        let reply = self
+            .xcb_connection()
+            .xinput_xi_grab_device(
+                window,
+                x11rb::CURRENT_TIME,
+                x11rb::NONE,
+                device_id,
+                xproto::GrabMode::ASYNC,
+                xproto::GrabMode::ASYNC,
+                xinput::GrabOwner::NO_OWNER,
+                &[mask.into()],
+            )?
+            .reply()?;
+
+        Ok(reply.status == xproto::GrabStatus::SUCCESS)
+    }
+
+    /// Release a grab of [`XConnection::grab_device`].
+    pub fn ungrab_device(&self, device_id: u16) -> Result<(), X11Error> {
+        self.xcb_connection()
+            .xinput_xi_ungrab_device(x11rb::CURRENT_TIME, device_id)?
+            .check()?;
+        Ok(())
+    }
+
     pub fn select_xkb_events(
         &self,
         device_id: xkb::DeviceSpec,
@@ -61,6 +94,23 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// Inject an input event with the XTest extension.
+    ///
+    /// The `root`, `x` and `y` are the position of the pointer for the motion events.
+    pub fn fake_input(
+        &self,
+        event_type: u8,
+        detail: u8,
+        root: xproto::Window,
+        x: i16,
+        y: i16,
+    ) -> Result<(), X11Error> {
+        self.xcb_connection()
+            .xtest_fake_input(event_type, detail, x11rb::CURRENT_TIME, root, x, y, 0)?
+            .check()?;
+        Ok(())
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
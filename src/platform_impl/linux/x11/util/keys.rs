@@ -1,6 +1,10 @@
 use std::{iter::Enumerate, slice::Iter};
 
+use x11rb::protocol::xkb::{self, ConnectionExt as _};
+
 use super::*;
+use crate::keyboard::KeyboardLayoutInfo;
+use crate::platform_impl::common::xkb_state::KbdState;
 
 pub struct Keymap {
     keys: [u8; 32],
@@ -71,6 +75,71 @@ impl XConnection {
 
         Keymap { keys }
     }
+
+    /// The layouts of the keymap, with their RMLVO names when the server sets them.
+    pub fn keyboard_layout_info(&self, kb_state: &KbdState) -> KeyboardLayoutInfo {
+        let mut info = kb_state.keyboard_layout_info();
+        if kb_state.has_custom_keymap() {
+            return info;
+        }
+
+        let atoms = self.atoms();
+        let rules_names = self
+            .get_property::<u8>(
+                self.default_root().root,
+                atoms[_XKB_RULES_NAMES],
+                xproto::AtomEnum::STRING.into(),
+            )
+            .unwrap_or_default();
+
+        // The names are the rules, the model, the layouts, the variants and the options.
+        let mut names = rules_names
+            .split(|&byte| byte == 0)
+            .map(String::from_utf8_lossy)
+            .skip(2);
+        let layouts = names.next().unwrap_or_default();
+        let variants = names.next().unwrap_or_default();
+        let mut variants = variants.split(',');
+        for (layout, name) in info.layouts.iter_mut().zip(layouts.split(',')) {
+            let variant = variants.next().unwrap_or_default();
+            layout.layout = (!name.is_empty()).then(|| name.to_owned());
+            layout.variant = (!variant.is_empty()).then(|| variant.to_owned());
+        }
+
+        info
+    }
+
+    /// Whether the scroll lock indicator of the core keyboard is on.
+    pub fn scroll_lock(&self) -> bool {
+        let atoms = self.atoms();
+        self.xcb_connection()
+            .xkb_get_named_indicator(
+                xkb::ID::USE_CORE_KBD.into(),
+                xkb::LedClass::DFLT_XI_CLASS,
+                xkb::ID::DFLT_XI_ID,
+                atoms[ScrollLock],
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .is_some_and(|reply| reply.found && reply.on)
+    }
+
+    /// Lock the group of the core keyboard, switching its layout.
+    pub fn lock_keyboard_group(&self, group: u8) -> Result<(), X11Error> {
+        self.xcb_connection()
+            .xkb_latch_lock_state(
+                xkb::ID::USE_CORE_KBD.into(),
+                xproto::ModMask::from(0u16),
+                xproto::ModMask::from(0u16),
+                true,
+                xkb::Group::from(group),
+                xproto::ModMask::from(0u16),
+                false,
+                0,
+            )?
+            .check()?;
+        Ok(())
+    }
 }
 
 fn first_bit(b: u8) -> u8 {
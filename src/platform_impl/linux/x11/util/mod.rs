@@ -3,17 +3,24 @@
 
 mod client_msg;
 mod cursor;
+mod edid;
 mod geometry;
 mod hint;
+mod hotkey;
 mod icon;
 mod input;
 pub mod keys;
 pub(crate) mod memory;
+mod popup;
 mod randr;
 mod window_property;
 mod wm;
+mod xsettings;
 
-pub use self::{cursor::*, geometry::*, hint::*, input::*, window_property::*, wm::*};
+pub use self::{
+    cursor::*, edid::*, geometry::*, hint::*, hotkey::*, input::*, popup::*, window_property::*,
+    wm::*, xsettings::*,
+};
 
 use std::{
     mem::{self, MaybeUninit},
@@ -0,0 +1,209 @@
+//! Placement of the popups, following the semantics of the `xdg_positioner`.
+
+use crate::window::{ConstraintAdjustment, PopupAnchor, PopupGravity};
+
+/// The rectangle as `(x, y, width, height)`.
+pub type PopupRect = (i32, i32, i32, i32);
+
+/// Compute the geometry of the popup of the given `size` attached to the `anchor_rect`.
+///
+/// When the popup doesn't fit into the `bounds`, the `constraint_adjustment` is applied on each
+/// axis, trying to flip, slide and then resize the popup.
+pub fn popup_geometry(
+    anchor_rect: PopupRect,
+    anchor: PopupAnchor,
+    gravity: PopupGravity,
+    size: (i32, i32),
+    constraint_adjustment: ConstraintAdjustment,
+    bounds: PopupRect,
+) -> PopupRect {
+    let (anchor_x, anchor_y) = anchor_sides(anchor);
+    let (gravity_x, gravity_y) = gravity_sides(gravity);
+
+    let (x, width) = place_on_axis(
+        (anchor_rect.0, anchor_rect.2),
+        (anchor_x, gravity_x),
+        size.0,
+        (bounds.0, bounds.2),
+        (
+            constraint_adjustment.contains(ConstraintAdjustment::FLIP_X),
+            constraint_adjustment.contains(ConstraintAdjustment::SLIDE_X),
+            constraint_adjustment.contains(ConstraintAdjustment::RESIZE_X),
+        ),
+    );
+    let (y, height) = place_on_axis(
+        (anchor_rect.1, anchor_rect.3),
+        (anchor_y, gravity_y),
+        size.1,
+        (bounds.1, bounds.3),
+        (
+            constraint_adjustment.contains(ConstraintAdjustment::FLIP_Y),
+            constraint_adjustment.contains(ConstraintAdjustment::SLIDE_Y),
+            constraint_adjustment.contains(ConstraintAdjustment::RESIZE_Y),
+        ),
+    );
+
+    (x, y, width, height)
+}
+
+/// Place the popup on a single axis, returning its start and its length.
+///
+/// The sides are `-1` for the start of the axis, `0` for the center, and `1` for the end.
+fn place_on_axis(
+    (rect_start, rect_len): (i32, i32),
+    (anchor, gravity): (i32, i32),
+    size: i32,
+    (bound_start, bound_len): (i32, i32),
+    (flip, slide, resize): (bool, bool, bool),
+) -> (i32, i32) {
+    let bound_end = bound_start + bound_len;
+    let position = |anchor: i32, gravity: i32| {
+        let point = rect_start + (anchor + 1) * rect_len / 2;
+        point + (gravity - 1) * size / 2
+    };
+    let fits = |start: i32, size: i32| start >= bound_start && start + size <= bound_end;
+
+    let start = position(anchor, gravity);
+    if fits(start, size) {
+        return (start, size);
+    }
+
+    if flip {
+        let flipped = position(-anchor, -gravity);
+        if fits(flipped, size) {
+            return (flipped, size);
+        }
+    }
+
+    let start = if slide {
+        let slid = start.min(bound_end - size).max(bound_start);
+        if fits(slid, size) {
+            return (slid, size);
+        }
+        slid
+    } else {
+        start
+    };
+
+    if resize {
+        let (visible_start, visible_end) = (start.max(bound_start), (start + size).min(bound_end));
+        if visible_end > visible_start {
+            return (visible_start, visible_end - visible_start);
+        }
+    }
+
+    (start, size)
+}
+
+fn anchor_sides(anchor: PopupAnchor) -> (i32, i32) {
+    match anchor {
+        PopupAnchor::None => (0, 0),
+        PopupAnchor::Top => (0, -1),
+        PopupAnchor::Bottom => (0, 1),
+        PopupAnchor::Left => (-1, 0),
+        PopupAnchor::Right => (1, 0),
+        PopupAnchor::TopLeft => (-1, -1),
+        PopupAnchor::BottomLeft => (-1, 1),
+        PopupAnchor::TopRight => (1, -1),
+        PopupAnchor::BottomRight => (1, 1),
+    }
+}
+
+fn gravity_sides(gravity: PopupGravity) -> (i32, i32) {
+    match gravity {
+        PopupGravity::None => (0, 0),
+        PopupGravity::Top => (0, -1),
+        PopupGravity::Bottom => (0, 1),
+        PopupGravity::Left => (-1, 0),
+        PopupGravity::Right => (1, 0),
+        PopupGravity::TopLeft => (-1, -1),
+        PopupGravity::BottomLeft => (-1, 1),
+        PopupGravity::TopRight => (1, -1),
+        PopupGravity::BottomRight => (1, 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: PopupRect = (0, 0, 1000, 800);
+
+    #[test]
+    fn menu_below_the_button() {
+        let geometry = popup_geometry(
+            (100, 50, 80, 20),
+            PopupAnchor::BottomLeft,
+            PopupGravity::BottomRight,
+            (200, 300),
+            ConstraintAdjustment::empty(),
+            SCREEN,
+        );
+        assert_eq!(geometry, (100, 70, 200, 300));
+    }
+
+    #[test]
+    fn centered_tooltip() {
+        let geometry = popup_geometry(
+            (100, 100, 100, 100),
+            PopupAnchor::None,
+            PopupGravity::None,
+            (50, 20),
+            ConstraintAdjustment::empty(),
+            SCREEN,
+        );
+        assert_eq!(geometry, (125, 140, 50, 20));
+    }
+
+    #[test]
+    fn flip_above_the_button() {
+        let geometry = popup_geometry(
+            (100, 700, 80, 20),
+            PopupAnchor::BottomLeft,
+            PopupGravity::BottomRight,
+            (200, 300),
+            ConstraintAdjustment::FLIP_Y,
+            SCREEN,
+        );
+        assert_eq!(geometry, (100, 400, 200, 300));
+    }
+
+    #[test]
+    fn slide_into_the_screen() {
+        let geometry = popup_geometry(
+            (900, 100, 80, 20),
+            PopupAnchor::BottomLeft,
+            PopupGravity::BottomRight,
+            (200, 300),
+            ConstraintAdjustment::SLIDE_X,
+            SCREEN,
+        );
+        assert_eq!(geometry, (800, 120, 200, 300));
+    }
+
+    #[test]
+    fn resize_to_the_visible_area() {
+        let geometry = popup_geometry(
+            (100, 600, 80, 20),
+            PopupAnchor::BottomLeft,
+            PopupGravity::BottomRight,
+            (200, 650),
+            ConstraintAdjustment::FLIP_Y | ConstraintAdjustment::RESIZE_Y,
+            (0, 0, 1000, 700),
+        );
+        assert_eq!(geometry, (100, 620, 200, 80));
+    }
+
+    #[test]
+    fn unconstrained_popup_stays_off_screen() {
+        let geometry = popup_geometry(
+            (900, 100, 80, 20),
+            PopupAnchor::BottomLeft,
+            PopupGravity::BottomRight,
+            (200, 300),
+            ConstraintAdjustment::empty(),
+            SCREEN,
+        );
+        assert_eq!(geometry, (900, 120, 200, 300));
+    }
+}
@@ -1,8 +1,10 @@
-use std::{env, str, str::FromStr};
+use std::{collections::hash_map::Entry, env, str, str::FromStr};
 
 use super::*;
 use crate::platform_impl::platform::x11::monitor;
-use crate::{dpi::validate_scale_factor, platform_impl::platform::x11::VideoModeHandle};
+use crate::{
+    dpi::validate_scale_factor, monitor::GammaRamp, platform_impl::platform::x11::VideoModeHandle,
+};
 
 use log::warn;
 use x11rb::protocol::randr::{self, ConnectionExt as _};
@@ -46,7 +48,7 @@ impl XConnection {
         &self,
         resources: &monitor::ScreenResources,
         crtc: &randr::GetCrtcInfoReply,
-    ) -> Option<(String, f64, Vec<VideoModeHandle>)> {
+    ) -> Option<monitor::OutputInfo> {
         let output_info = match self
             .xcb_connection()
             .randr_get_output_info(crtc.outputs[0], x11rb::CURRENT_TIME)
@@ -128,7 +130,10 @@ impl XConnection {
                 dpi_override
             }
             EnvVarDPI::NotSet => {
-                if let Some(dpi) = self.get_xft_dpi() {
+                let xsettings_scale_factor = self.xsettings().scale_factor();
+                if let Some(scale_factor) = xsettings_scale_factor {
+                    scale_factor
+                } else if let Some(dpi) = self.get_xft_dpi() {
                     dpi / 96.
                 } else {
                     calc_dpi_factor(
@@ -139,7 +144,46 @@ impl XConnection {
             }
         };
 
-        Some((name, scale_factor, modes))
+        let physical_size_mm = (output_info.mm_width != 0 && output_info.mm_height != 0)
+            .then_some((output_info.mm_width, output_info.mm_height));
+        let edid = self.get_output_edid(crtc.outputs[0]);
+
+        Some(monitor::OutputInfo {
+            name,
+            scale_factor,
+            video_modes: modes,
+            physical_size_mm,
+            edid,
+        })
+    }
+
+    /// Get the raw EDID of the output.
+    pub fn get_output_edid(&self, output: randr::Output) -> Option<Vec<u8>> {
+        // The EDID is at most 256 blocks of 128 bytes, the length is in 32-bit units.
+        let property = self
+            .xcb_connection()
+            .randr_get_output_property(
+                output,
+                self.atoms()[Edid],
+                xproto::AtomEnum::ANY,
+                0,
+                256 * 128 / 4,
+                false,
+                false,
+            )
+            .map_err(X11Error::from)
+            .and_then(|r| r.reply().map_err(X11Error::from));
+
+        match property {
+            Ok(property) if property.format == 8 && !property.data.is_empty() => {
+                Some(property.data)
+            }
+            Ok(_) => None,
+            Err(err) => {
+                warn!("Failed to get output EDID: {:?}", err);
+                None
+            }
+        }
     }
 
     pub fn set_crtc_config(
@@ -168,6 +212,113 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    pub fn get_crtc_gamma(&self, crtc_id: randr::Crtc) -> Result<GammaRamp, X11Error> {
+        let gamma = self
+            .xcb_connection()
+            .randr_get_crtc_gamma(crtc_id)?
+            .reply()?;
+
+        Ok(GammaRamp {
+            red: gamma.red,
+            green: gamma.green,
+            blue: gamma.blue,
+        })
+    }
+
+    pub fn set_crtc_gamma(&self, crtc_id: randr::Crtc, ramp: &GammaRamp) -> Result<(), X11Error> {
+        let size = self
+            .xcb_connection()
+            .randr_get_crtc_gamma_size(crtc_id)?
+            .reply()?
+            .size;
+        let ramp = ramp.resample(size as usize);
+
+        self.xcb_connection()
+            .randr_set_crtc_gamma(crtc_id, &ramp.red, &ramp.green, &ramp.blue)?
+            .check()
+            .map_err(Into::into)
+    }
+
+    /// Set the gamma ramp of the CRTC, saving the original one to restore it once the fullscreen
+    /// window on the CRTC leaves the fullscreen.
+    pub fn set_gamma_ramp(&self, crtc_id: randr::Crtc, ramp: &GammaRamp) -> Result<(), X11Error> {
+        let window = self
+            .fullscreen_windows
+            .lock()
+            .unwrap()
+            .get(&crtc_id)
+            .copied();
+
+        let mut saved_gamma_ramps = self.saved_gamma_ramps.lock().unwrap();
+        match saved_gamma_ramps.entry(crtc_id) {
+            Entry::Vacant(entry) => {
+                entry.insert((self.get_crtc_gamma(crtc_id)?, window));
+            }
+            Entry::Occupied(mut entry) => entry.get_mut().1 = window,
+        }
+
+        self.set_crtc_gamma(crtc_id, ramp)
+    }
+
+    /// Make the window the fullscreen window of the CRTC, restoring the gamma ramps it owns on the
+    /// other CRTCs.
+    pub fn set_fullscreen_window(&self, window: xproto::Window, crtc_id: randr::Crtc) {
+        self.remove_fullscreen_window(window, Some(crtc_id));
+        self.fullscreen_windows
+            .lock()
+            .unwrap()
+            .insert(crtc_id, window);
+    }
+
+    /// The window left the fullscreen or was destroyed, restore the gamma ramps it owns, except on
+    /// the kept CRTC.
+    pub fn remove_fullscreen_window(&self, window: xproto::Window, kept_crtc: Option<randr::Crtc>) {
+        self.fullscreen_windows
+            .lock()
+            .unwrap()
+            .retain(|&crtc_id, &mut fullscreen_window| {
+                fullscreen_window != window || Some(crtc_id) == kept_crtc
+            });
+
+        let restored_crtcs: Vec<_> = {
+            let saved_gamma_ramps = self.saved_gamma_ramps.lock().unwrap();
+            saved_gamma_ramps
+                .iter()
+                .filter(|&(&crtc_id, &(_, owner))| {
+                    owner == Some(window) && Some(crtc_id) != kept_crtc
+                })
+                .map(|(&crtc_id, _)| crtc_id)
+                .collect()
+        };
+
+        for crtc_id in restored_crtcs {
+            self.restore_gamma_ramp(crtc_id);
+        }
+    }
+
+    /// Restore all the gamma ramps changed with [`Self::set_gamma_ramp`].
+    pub fn restore_gamma_ramps(&self) {
+        let crtc_ids: Vec<_> = self
+            .saved_gamma_ramps
+            .lock()
+            .unwrap()
+            .keys()
+            .copied()
+            .collect();
+        for crtc_id in crtc_ids {
+            self.restore_gamma_ramp(crtc_id);
+        }
+    }
+
+    fn restore_gamma_ramp(&self, crtc_id: randr::Crtc) {
+        let saved_gamma_ramp = self.saved_gamma_ramps.lock().unwrap().remove(&crtc_id);
+        if let Some((ramp, _)) = saved_gamma_ramp {
+            if let Err(err) = self.set_crtc_gamma(crtc_id, &ramp) {
+                warn!("Failed to restore gamma ramp: {:?}", err);
+            }
+        }
+    }
+
     pub fn get_crtc_mode(&self, crtc_id: randr::Crtc) -> Result<randr::Mode, X11Error> {
         Ok(self
             .xcb_connection()
@@ -0,0 +1,514 @@
+//! Client side of the XSETTINGS protocol.
+//!
+//! The settings are stored by the manager owning the `_XSETTINGS_S<screen>` selection in the
+//! `_XSETTINGS_SETTINGS` property of its window.
+
+use std::collections::HashMap;
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+use x11rb::protocol::xkb::{self, ConnectionExt as _};
+
+use super::*;
+use crate::dpi::validate_scale_factor;
+use crate::event_loop::SystemSettings;
+use crate::window::{AccentColor, Theme};
+
+/// A single XSETTINGS value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XSetting {
+    Integer(i32),
+    String(String),
+    Color {
+        red: u16,
+        green: u16,
+        blue: u16,
+        alpha: u16,
+    },
+}
+
+/// The settings exported by the XSETTINGS manager.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XSettings {
+    settings: HashMap<String, XSetting>,
+}
+
+/// The serialized settings are malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XSettingsParseError;
+
+impl XSettings {
+    /// Parse the content of the `_XSETTINGS_SETTINGS` property.
+    pub fn parse(data: &[u8]) -> Result<Self, XSettingsParseError> {
+        let mut reader = Reader {
+            data,
+            big_endian: false,
+        };
+
+        reader.big_endian = match reader.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(XSettingsParseError),
+        };
+        reader.skip(3)?;
+        let _serial = reader.read_u32()?;
+        let count = reader.read_u32()?;
+
+        let mut settings = HashMap::new();
+        for _ in 0..count {
+            let kind = reader.read_u8()?;
+            reader.skip(1)?;
+            let name_len = reader.read_u16()? as usize;
+            let name = reader.read_string(name_len)?;
+            let _last_change_serial = reader.read_u32()?;
+
+            let value = match kind {
+                0 => XSetting::Integer(reader.read_u32()? as i32),
+                1 => {
+                    let len = reader.read_u32()? as usize;
+                    XSetting::String(reader.read_string(len)?)
+                }
+                2 => XSetting::Color {
+                    red: reader.read_u16()?,
+                    blue: reader.read_u16()?,
+                    green: reader.read_u16()?,
+                    alpha: reader.read_u16()?,
+                },
+                _ => return Err(XSettingsParseError),
+            };
+            settings.insert(name, value);
+        }
+
+        Ok(Self { settings })
+    }
+
+    pub fn integer(&self, name: &str) -> Option<i32> {
+        match self.settings.get(name)? {
+            XSetting::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.settings.get(name)? {
+            XSetting::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The `Xft/DPI`, stored in 1024ths of dots per inch.
+    pub fn dpi(&self) -> Option<f64> {
+        // `-1` means the default DPI.
+        self.integer("Xft/DPI")
+            .filter(|&dpi| dpi > 0)
+            .map(|dpi| dpi as f64 / 1024.)
+    }
+
+    /// The integer scale of the windows of GTK.
+    pub fn window_scaling_factor(&self) -> Option<u32> {
+        self.integer("Gdk/WindowScalingFactor")
+            .filter(|&scale| scale > 0)
+            .map(|scale| scale as u32)
+    }
+
+    /// The scale factor, from the DPI or from the window scaling factor when there's no DPI.
+    ///
+    /// The `Xft/DPI` already includes the window scaling factor, e.g. it's 192 with the 200%
+    /// scaling of GNOME.
+    pub fn scale_factor(&self) -> Option<f64> {
+        let scale_factor = match self.dpi() {
+            Some(dpi) => dpi / 96.,
+            None => self.window_scaling_factor()? as f64,
+        };
+        validate_scale_factor(scale_factor).then_some(scale_factor)
+    }
+
+    /// The maximum time between the clicks of a double click.
+    pub fn double_click_time(&self) -> Option<Duration> {
+        self.integer("Net/DoubleClickTime")
+            .filter(|&time| time >= 0)
+            .map(|time| Duration::from_millis(time as u64))
+    }
+
+    /// The maximum distance between the clicks of a double click.
+    pub fn double_click_distance(&self) -> Option<u32> {
+        self.integer("Net/DoubleClickDistance")
+            .filter(|&distance| distance >= 0)
+            .map(|distance| distance as u32)
+    }
+
+    /// The time between the blinks of the text caret, zero when it doesn't blink.
+    pub fn caret_blink_interval(&self) -> Option<Duration> {
+        if self.integer("Net/CursorBlink") == Some(0) {
+            return Some(Duration::ZERO);
+        }
+
+        // The time is for the whole cycle, the caret blinks twice.
+        self.integer("Net/CursorBlinkTime")
+            .filter(|&time| time > 0)
+            .map(|time| Duration::from_millis(time as u64 / 2))
+    }
+
+    /// Whether the animations were disabled.
+    pub fn reduce_motion(&self) -> Option<bool> {
+        self.integer("Gtk/EnableAnimations")
+            .map(|enabled| enabled == 0)
+    }
+
+    /// The name of the Xcursor theme.
+    pub fn cursor_theme_name(&self) -> Option<&str> {
+        self.string("Gtk/CursorThemeName")
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The settings exposed through [`EventLoopWindowTargetExtX11::xsettings`].
+    ///
+    /// [`EventLoopWindowTargetExtX11::xsettings`]: crate::platform::x11::EventLoopWindowTargetExtX11::xsettings
+    pub fn to_public(&self) -> crate::platform::x11::XSettings {
+        crate::platform::x11::XSettings {
+            dpi: self.dpi(),
+            window_scaling_factor: self.window_scaling_factor(),
+            double_click_time: self.double_click_time(),
+            cursor_theme_name: self.cursor_theme_name().map(str::to_owned),
+        }
+    }
+
+    /// The theme variant, from the name of the GTK theme.
+    pub fn theme(&self) -> Option<Theme> {
+        let name = self.string("Net/ThemeName")?.to_ascii_lowercase();
+        if name.contains("dark") {
+            Some(Theme::Dark)
+        } else {
+            Some(Theme::Light)
+        }
+    }
+
+    /// The accent color, from the selection color of the GTK color scheme.
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        // Entries like `selected_bg_color: #3584e4`, separated by new lines or semicolons.
+        self.string("Gtk/ColorScheme")?
+            .split(['\n', ';'])
+            .filter_map(|entry| entry.split_once(':'))
+            .find(|(name, _)| name.trim() == "selected_bg_color")
+            .and_then(|(_, color)| parse_color(color.trim()))
+    }
+}
+
+/// Parse a `#rrggbb` or a `#rrrrggggbbbb` color.
+fn parse_color(color: &str) -> Option<AccentColor> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.is_ascii() {
+        return None;
+    }
+
+    let digits = match hex.len() {
+        6 => 2,
+        12 => 4,
+        _ => return None,
+    };
+    let channel = |index: usize| {
+        let digits = &hex[index * digits..][..2];
+        u8::from_str_radix(digits, 16).ok()
+    };
+
+    Some(AccentColor {
+        red: channel(0)?,
+        green: channel(1)?,
+        blue: channel(2)?,
+    })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], XSettingsParseError> {
+        if self.data.len() < len {
+            return Err(XSettingsParseError);
+        }
+
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), XSettingsParseError> {
+        self.take(len).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, XSettingsParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, XSettingsParseError> {
+        let bytes = self.take(2)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn read_u32(&mut self) -> Result<u32, XSettingsParseError> {
+        let bytes = self.take(4)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    /// Read a string padded to 4 bytes.
+    fn read_string(&mut self, len: usize) -> Result<String, XSettingsParseError> {
+        let string = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.skip((4 - len % 4) % 4)?;
+        Ok(string)
+    }
+}
+
+impl XConnection {
+    /// The `_XSETTINGS_S<screen>` selection of the default screen.
+    pub fn xsettings_selection(&self) -> Result<xproto::Atom, X11Error> {
+        let selection = format!("_XSETTINGS_S{}", self.default_screen_index());
+        let selection = self
+            .xcb_connection()
+            .intern_atom(false, selection.as_bytes())?
+            .reply()?
+            .atom;
+        Ok(selection)
+    }
+
+    /// Find the owner of the XSETTINGS selection and watch its settings.
+    pub fn update_xsettings_owner(&self) -> Result<Option<xproto::Window>, X11Error> {
+        let selection = self.xsettings_selection()?;
+        let owner = self
+            .xcb_connection()
+            .get_selection_owner(selection)?
+            .reply()?
+            .owner;
+        let owner = (owner != x11rb::NONE).then_some(owner);
+
+        if let Some(owner) = owner {
+            self.xcb_connection()
+                .change_window_attributes(
+                    owner,
+                    &xproto::ChangeWindowAttributesAux::new()
+                        .event_mask(xproto::EventMask::PROPERTY_CHANGE),
+                )?
+                .check()?;
+        }
+
+        *self.xsettings_owner.lock().unwrap() = owner;
+        Ok(owner)
+    }
+
+    /// The window of the XSETTINGS manager.
+    pub fn xsettings_owner(&self) -> Option<xproto::Window> {
+        *self.xsettings_owner.lock().unwrap()
+    }
+
+    /// The system settings, from the XSETTINGS and the repeat controls of the core keyboard.
+    pub fn system_settings(&self) -> SystemSettings {
+        let settings = self.xsettings();
+        let mut system_settings = SystemSettings {
+            double_click_interval: settings.double_click_time(),
+            double_click_distance: settings.double_click_distance(),
+            caret_blink_interval: settings.caret_blink_interval(),
+            reduce_motion: settings.reduce_motion(),
+            ..Default::default()
+        };
+        drop(settings);
+
+        let controls = self
+            .xcb_connection()
+            .xkb_get_controls(xkb::ID::USE_CORE_KBD.into())
+            .map_err(X11Error::from)
+            .and_then(|r| r.reply().map_err(X11Error::from));
+        match controls {
+            Ok(controls)
+                if controls
+                    .enabled_controls
+                    .contains(xkb::BoolCtrl::REPEAT_KEYS) =>
+            {
+                system_settings.key_repeat_delay =
+                    Some(Duration::from_millis(controls.repeat_delay as u64));
+                system_settings.key_repeat_interval =
+                    Some(Duration::from_millis(controls.repeat_interval as u64));
+            }
+            Ok(_) => (),
+            Err(err) => log::warn!("Failed to get the XKB controls: {err:?}"),
+        }
+
+        system_settings
+    }
+
+    /// The current XSETTINGS.
+    pub fn xsettings(&self) -> MutexGuard<'_, XSettings> {
+        self.xsettings.lock().unwrap()
+    }
+
+    /// The system theme, from the `color-scheme` of the XDG desktop portal, or guessed from the
+    /// name of the GTK theme when the portal has no preference.
+    pub fn system_theme(&self) -> Option<Theme> {
+        let color_scheme = *self.color_scheme.lock().unwrap();
+        color_scheme.or_else(|| self.xsettings().theme())
+    }
+
+    /// Read the settings from the XSETTINGS manager, returning the previous ones.
+    pub fn reload_xsettings(&self) -> XSettings {
+        let atoms = self.atoms();
+        let settings = match self.xsettings_owner() {
+            Some(owner) => self
+                .get_property::<u8>(
+                    owner,
+                    atoms[_XSETTINGS_SETTINGS],
+                    atoms[_XSETTINGS_SETTINGS],
+                )
+                .map_err(|err| log::warn!("Failed to get XSETTINGS: {err:?}"))
+                .and_then(|data| {
+                    XSettings::parse(&data).map_err(|_| log::warn!("Malformed XSETTINGS"))
+                })
+                .unwrap_or_default(),
+            None => XSettings::default(),
+        };
+
+        std::mem::replace(&mut *self.xsettings.lock().unwrap(), settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serialize(settings: &[(&str, XSetting)]) -> Vec<u8> {
+        fn push_string(data: &mut Vec<u8>, string: &str) {
+            data.extend_from_slice(string.as_bytes());
+            data.resize(data.len() + (4 - string.len() % 4) % 4, 0);
+        }
+
+        let mut data = vec![1, 0, 0, 0];
+        data.extend_from_slice(&7u32.to_be_bytes());
+        data.extend_from_slice(&(settings.len() as u32).to_be_bytes());
+        for (name, value) in settings {
+            let kind = match value {
+                XSetting::Integer(_) => 0,
+                XSetting::String(_) => 1,
+                XSetting::Color { .. } => 2,
+            };
+            data.extend_from_slice(&[kind, 0]);
+            data.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            push_string(&mut data, name);
+            data.extend_from_slice(&0u32.to_be_bytes());
+            match value {
+                XSetting::Integer(value) => data.extend_from_slice(&value.to_be_bytes()),
+                XSetting::String(value) => {
+                    data.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                    push_string(&mut data, value);
+                }
+                XSetting::Color {
+                    red,
+                    green,
+                    blue,
+                    alpha,
+                } => {
+                    for channel in [red, blue, green, alpha] {
+                        data.extend_from_slice(&channel.to_be_bytes());
+                    }
+                }
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn parse_settings() {
+        let color = XSetting::Color {
+            red: 1,
+            green: 2,
+            blue: 3,
+            alpha: 4,
+        };
+        let data = serialize(&[
+            ("Net/ThemeName", XSetting::String("Adwaita-dark".into())),
+            ("Xft/DPI", XSetting::Integer(98304)),
+            ("Gtk/Color", color.clone()),
+        ]);
+
+        let settings = XSettings::parse(&data).unwrap();
+        assert_eq!(settings.string("Net/ThemeName"), Some("Adwaita-dark"));
+        assert_eq!(settings.integer("Xft/DPI"), Some(98304));
+        assert_eq!(settings.settings.get("Gtk/Color"), Some(&color));
+        assert_eq!(settings.theme(), Some(Theme::Dark));
+    }
+
+    #[test]
+    fn parse_truncated() {
+        let data = serialize(&[("Net/ThemeName", XSetting::String("Adwaita".into()))]);
+        assert_eq!(
+            XSettings::parse(&data[..data.len() - 4]),
+            Err(XSettingsParseError)
+        );
+    }
+
+    #[test]
+    fn scale_factor() {
+        let data = serialize(&[
+            ("Xft/DPI", XSetting::Integer(196608)),
+            ("Gdk/WindowScalingFactor", XSetting::Integer(2)),
+            ("Net/DoubleClickTime", XSetting::Integer(400)),
+        ]);
+        let settings = XSettings::parse(&data).unwrap();
+        assert_eq!(settings.scale_factor(), Some(2.));
+        assert_eq!(
+            settings.double_click_time(),
+            Some(Duration::from_millis(400))
+        );
+
+        // The window scaling factor is used without the DPI.
+        let data = serialize(&[
+            ("Xft/DPI", XSetting::Integer(-1)),
+            ("Gdk/WindowScalingFactor", XSetting::Integer(3)),
+        ]);
+        assert_eq!(XSettings::parse(&data).unwrap().scale_factor(), Some(3.));
+    }
+
+    #[test]
+    fn caret_blink() {
+        let data = serialize(&[
+            ("Net/CursorBlink", XSetting::Integer(1)),
+            ("Net/CursorBlinkTime", XSetting::Integer(1200)),
+        ]);
+        assert_eq!(
+            XSettings::parse(&data).unwrap().caret_blink_interval(),
+            Some(Duration::from_millis(600))
+        );
+
+        let data = serialize(&[
+            ("Net/CursorBlink", XSetting::Integer(0)),
+            ("Net/CursorBlinkTime", XSetting::Integer(1200)),
+        ]);
+        assert_eq!(
+            XSettings::parse(&data).unwrap().caret_blink_interval(),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn accent_color() {
+        let scheme = "fg_color: #000000\nselected_bg_color: #3584e4;selected_fg_color: #ffffff";
+        let data = serialize(&[("Gtk/ColorScheme", XSetting::String(scheme.into()))]);
+
+        let settings = XSettings::parse(&data).unwrap();
+        assert_eq!(
+            settings.accent_color(),
+            Some(AccentColor {
+                red: 0x35,
+                green: 0x84,
+                blue: 0xe4,
+            })
+        );
+        assert_eq!(parse_color("#35358484e4e4"), settings.accent_color());
+    }
+}
@@ -5,6 +5,7 @@ use std::{
     os::raw::*,
     path::Path,
     sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
 };
 
 use log::{debug, info, warn};
@@ -14,6 +15,7 @@ use x11rb::{
     protocol::{
         randr,
         shape::SK,
+        sync::{self, ConnectionExt as _},
         xfixes::{ConnectionExt, RegionWrapper},
         xinput,
         xproto::{self, ConnectionExt as _, Rectangle},
@@ -24,8 +26,9 @@ use crate::{
     cursor::{Cursor, CustomCursor as RootCustomCursor},
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
-    event::{Event, InnerSizeWriter, WindowEvent},
+    event::{ElementState, Event, InnerSizeWriter, MouseButton, WindowEvent},
     event_loop::AsyncRequestSerial,
+    keyboard::PhysicalKey,
     platform::x11::WindowType,
     platform_impl::{
         x11::{
@@ -36,8 +39,9 @@ use crate::{
         PlatformIcon, VideoModeHandle as PlatformVideoModeHandle,
     },
     window::{
-        CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-        WindowButtons, WindowLevel,
+        AccentColor, ContentType, CursorGrabMode, ImeHints, ImePurpose, InjectedPointer,
+        PopupAttributes, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+        WindowAttributes, WindowButtons, WindowLevel,
     },
 };
 
@@ -75,6 +79,37 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub sync_request: SyncRequest,
+    pub frame: FrameState,
+    // The theme set by the user, the system one is followed when it's `None`
+    pub theme: Option<Theme>,
+}
+
+/// The state of the `_NET_WM_SYNC_REQUEST` protocol.
+///
+/// The request carries the value of the counter, and whether it's for the extended counter.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SyncRequest {
+    #[default]
+    None,
+    // Waiting for the `ConfigureNotify` the request was sent for.
+    Requested(i64, bool),
+    // Waiting for the window to be redrawn with the new size.
+    Configured(i64, bool),
+}
+
+/// The frames delimited by the extended `_NET_WM_SYNC_REQUEST` counter, which the compositor
+/// reports with `_NET_WM_FRAME_DRAWN` once they're drawn.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct FrameState {
+    // The value of the extended counter, odd while a frame is drawn.
+    pub counter_value: i64,
+    // The counter value of the last frame, until the compositor has drawn it.
+    pub pending: Option<i64>,
+    // `request_frame` was called, the redraw is sent once the last frame is drawn.
+    pub requested: bool,
+    // The time the last frame was drawn, for the requested redraw.
+    pub time: Option<Duration>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -116,6 +151,9 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            sync_request: SyncRequest::None,
+            frame: FrameState::default(),
+            theme: None,
         })
     }
 }
@@ -139,6 +177,9 @@ pub(crate) struct UnownedWindow {
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<super::ActivationToken>,
+    sync_counter: Option<sync::Counter>,  // never changes
+    frame_counter: Option<sync::Counter>, // never changes
+    popup_grab: bool,                     // never changes
 }
 
 macro_rules! leap {
@@ -227,6 +268,20 @@ impl UnownedWindow {
             dimensions
         };
 
+        // Place the popup relative to its parent.
+        let (position, dimensions) = match window_attrs.popup.as_ref() {
+            Some(popup) => {
+                let (position, dimensions) = leap!(Self::place_popup(
+                    event_loop,
+                    popup,
+                    dimensions,
+                    scale_factor
+                ));
+                (Some(position), dimensions)
+            }
+            None => (position, dimensions),
+        };
+
         let screen_id = match window_attrs.platform_specific.x11.screen_id {
             Some(id) => id,
             None => xconn.default_screen_index() as c_int,
@@ -293,7 +348,8 @@ impl UnownedWindow {
 
             aux = aux.event_mask(event_mask).border_pixel(0);
 
-            if window_attrs.platform_specific.x11.override_redirect {
+            if window_attrs.platform_specific.x11.override_redirect || window_attrs.popup.is_some()
+            {
                 aux = aux.override_redirect(true as u32);
             }
 
@@ -374,6 +430,9 @@ impl UnownedWindow {
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            sync_counter: None,
+            frame_counter: None,
+            popup_grab: false,
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -383,6 +442,7 @@ impl UnownedWindow {
         leap!(window.set_decorations_inner(window_attrs.decorations)).ignore_error();
 
         if let Some(theme) = window_attrs.preferred_theme {
+            window.shared_state_lock().theme = Some(theme);
             leap!(window.set_theme_inner(Some(theme))).ignore_error();
         }
 
@@ -444,6 +504,28 @@ impl UnownedWindow {
             leap!(window.set_window_types(window_attrs.platform_specific.x11.x11_window_types))
                 .ignore_error();
 
+            // Make the window transient for its parent.
+            let transient_for = window_attrs
+                .platform_specific
+                .x11
+                .transient_for
+                .or_else(|| {
+                    window_attrs
+                        .popup
+                        .as_ref()
+                        .map(|popup| popup.parent.0 .0 as xproto::Window)
+                });
+            if let Some(parent) = transient_for {
+                leap!(xconn.change_property(
+                    window.xwindow,
+                    xproto::Atom::from(xproto::AtomEnum::WM_TRANSIENT_FOR),
+                    xproto::Atom::from(xproto::AtomEnum::WINDOW),
+                    xproto::PropMode::REPLACE,
+                    &[parent],
+                ))
+                .ignore_error();
+            }
+
             // Set size hints.
             let mut min_inner_size = window_attrs
                 .min_inner_size
@@ -501,18 +583,28 @@ impl UnownedWindow {
                 leap!(window.set_icon_inner(icon.inner)).ignore_error();
             }
 
+            // Create the counters for synchronizing the resizes and the frames with the window
+            // manager.
+            if xconn.sync_version().is_some() {
+                let frames = util::hint_is_supported(atoms[_NET_WM_FRAME_DRAWN]);
+                let (sync_counter, frame_counter) = leap!(window.create_sync_counters(frames));
+                window.sync_counter = Some(sync_counter);
+                window.frame_counter = frame_counter;
+            }
+
             // Opt into handling window close
+            let mut protocols = vec![atoms[WM_DELETE_WINDOW], atoms[_NET_WM_PING]];
+            if window.sync_counter.is_some() {
+                protocols.push(atoms[_NET_WM_SYNC_REQUEST]);
+            }
             let result = xconn.xcb_connection().change_property(
                 xproto::PropMode::REPLACE,
                 window.xwindow,
                 atoms[WM_PROTOCOLS],
                 xproto::AtomEnum::ATOM,
                 32,
-                2,
-                bytemuck::cast_slice::<xproto::Atom, u8>(&[
-                    atoms[WM_DELETE_WINDOW],
-                    atoms[_NET_WM_PING],
-                ]),
+                protocols.len() as u32,
+                bytemuck::cast_slice::<xproto::Atom, u8>(&protocols),
             );
             leap!(result).ignore_error();
 
@@ -524,6 +616,11 @@ impl UnownedWindow {
                     &xproto::ConfigureWindowAux::new().stack_mode(xproto::StackMode::ABOVE)
                 ))
                 .ignore_error();
+
+                // Grab the input for the popup, once it's mapped.
+                if matches!(window_attrs.popup, Some(PopupAttributes { grab: true, .. })) {
+                    window.popup_grab = window.grab_popup_input();
+                }
             }
 
             // Attempt to make keyboard input repeat detectable
@@ -598,6 +695,121 @@ impl UnownedWindow {
         Ok(window)
     }
 
+    /// Compute the position and the size of the popup in the root window coordinates.
+    fn place_popup(
+        event_loop: &EventLoopWindowTarget,
+        popup: &PopupAttributes,
+        size: (u32, u32),
+        scale_factor: f64,
+    ) -> Result<(PhysicalPosition<i32>, (u32, u32)), X11Error> {
+        let xconn = &event_loop.xconn;
+        let parent = popup.parent.0 .0 as xproto::Window;
+
+        // The anchor rectangle is in the coordinates of the parent.
+        let scale_factor = event_loop
+            .windows
+            .borrow()
+            .get(&popup.parent.0)
+            .and_then(|parent| parent.upgrade())
+            .map_or(scale_factor, |parent| parent.scale_factor());
+        let origin = xconn.translate_coords(parent, event_loop.root)?;
+        let (x, y): (i32, i32) = popup.anchor_rect.0.to_physical::<i32>(scale_factor).into();
+        let (width, height): (i32, i32) =
+            popup.anchor_rect.1.to_physical::<i32>(scale_factor).into();
+        let anchor_rect = (
+            origin.dst_x as i32 + x,
+            origin.dst_y as i32 + y,
+            width,
+            height,
+        );
+
+        // Keep the popup on the monitor with the anchor.
+        let bounds = xconn
+            .available_monitors()?
+            .into_iter()
+            .find(|monitor| {
+                monitor
+                    .rect
+                    .contains_point(anchor_rect.0 as i64, anchor_rect.1 as i64)
+            })
+            .map(|monitor| {
+                let (position, size) = (monitor.position(), monitor.size());
+                (
+                    position.x,
+                    position.y,
+                    size.width as i32,
+                    size.height as i32,
+                )
+            })
+            .unwrap_or((i32::MIN / 2, i32::MIN / 2, i32::MAX, i32::MAX));
+
+        let (x, y, width, height) = util::popup_geometry(
+            anchor_rect,
+            popup.anchor,
+            popup.gravity,
+            (size.0 as i32, size.1 as i32),
+            popup.constraint_adjustment,
+            bounds,
+        );
+
+        Ok((
+            PhysicalPosition::new(x, y),
+            (width.max(1) as u32, height.max(1) as u32),
+        ))
+    }
+
+    /// Grab the pointer and the keyboard for the popup, returning whether it succeeded.
+    fn grab_popup_input(&self) -> bool {
+        let pointer_mask = xinput::XIEventMask::MOTION
+            | xinput::XIEventMask::BUTTON_PRESS
+            | xinput::XIEventMask::BUTTON_RELEASE
+            | xinput::XIEventMask::ENTER
+            | xinput::XIEventMask::LEAVE;
+        let keyboard_mask = xinput::XIEventMask::KEY_PRESS
+            | xinput::XIEventMask::KEY_RELEASE
+            | xinput::XIEventMask::FOCUS_IN
+            | xinput::XIEventMask::FOCUS_OUT;
+
+        let pointer_grab =
+            self.xconn
+                .grab_device(self.xwindow, util::VIRTUAL_CORE_POINTER, pointer_mask);
+        let keyboard_grab =
+            self.xconn
+                .grab_device(self.xwindow, util::VIRTUAL_CORE_KEYBOARD, keyboard_mask);
+
+        match (pointer_grab, keyboard_grab) {
+            (Ok(true), Ok(true)) => true,
+            (pointer_grab, keyboard_grab) => {
+                warn!("Failed to grab the input for the popup");
+
+                // Don't keep a part of the grab.
+                if let Ok(true) = pointer_grab {
+                    let _ = self.xconn.ungrab_device(util::VIRTUAL_CORE_POINTER);
+                }
+                if let Ok(true) = keyboard_grab {
+                    let _ = self.xconn.ungrab_device(util::VIRTUAL_CORE_KEYBOARD);
+                }
+
+                false
+            }
+        }
+    }
+
+    /// Release the input grab of the popup.
+    pub(crate) fn ungrab_popup_input(&self) {
+        for device in [util::VIRTUAL_CORE_POINTER, util::VIRTUAL_CORE_KEYBOARD] {
+            if let Err(err) = self.xconn.ungrab_device(device) {
+                warn!("Failed to release the input grab of the popup: {err}");
+            }
+        }
+    }
+
+    /// Whether the window is a popup holding the input grab.
+    #[inline]
+    pub(crate) fn is_popup_grab(&self) -> bool {
+        self.popup_grab
+    }
+
     /// Embed this window into a parent window.
     pub(super) fn embed_window(&self) -> Result<(), RootOsError> {
         let atoms = self.xconn.atoms();
@@ -617,6 +829,156 @@ impl UnownedWindow {
         self.shared_state.lock().unwrap()
     }
 
+    /// Create the XSync counters and advertise them through `_NET_WM_SYNC_REQUEST_COUNTER`.
+    ///
+    /// The extended counter delimiting the frames is only created when `frames` is set.
+    fn create_sync_counters(
+        &self,
+        frames: bool,
+    ) -> Result<(sync::Counter, Option<sync::Counter>), X11Error> {
+        let atoms = self.xconn.atoms();
+        let mut counters = Vec::with_capacity(2);
+        for _ in 0..if frames { 2 } else { 1 } {
+            let counter = self.xconn.xcb_connection().generate_id()?;
+            self.xconn
+                .xcb_connection()
+                .sync_create_counter(counter, sync::Int64 { hi: 0, lo: 0 })?
+                .ignore_error();
+            counters.push(counter as util::Cardinal);
+        }
+        self.xconn
+            .change_property(
+                self.xwindow,
+                atoms[_NET_WM_SYNC_REQUEST_COUNTER],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &counters,
+            )?
+            .ignore_error();
+        Ok((counters[0], counters.get(1).copied()))
+    }
+
+    pub(super) fn sync_counter(&self) -> Option<sync::Counter> {
+        self.sync_counter
+    }
+
+    pub(super) fn frame_counter(&self) -> Option<sync::Counter> {
+        self.frame_counter
+    }
+
+    /// The window manager asked to be notified once the next configure is handled.
+    pub(super) fn handle_sync_request(&self, value: i64, extended: bool) {
+        if self.sync_counter.is_some() && (!extended || self.frame_counter.is_some()) {
+            self.shared_state_lock().sync_request = SyncRequest::Requested(value, extended);
+        }
+    }
+
+    /// The configure the sync request was sent for was handled.
+    ///
+    /// When the window was resized, the counter is updated once the window is redrawn.
+    pub(super) fn handle_sync_configure(&self, resized: bool) {
+        let mut shared_state_lock = self.shared_state_lock();
+        if let SyncRequest::Requested(value, extended) = shared_state_lock.sync_request {
+            shared_state_lock.sync_request = SyncRequest::Configured(value, extended);
+            drop(shared_state_lock);
+            if !resized {
+                self.update_sync_counter();
+            }
+        }
+    }
+
+    /// Update the sync counters once the window was redrawn, ending the frame.
+    ///
+    /// The counter the configure was requested for gets its value, and the extended counter is
+    /// made even so the compositor draws the frame.
+    pub(super) fn update_sync_counter(&self) {
+        let mut shared_state_lock = self.shared_state_lock();
+        let (counter, value) = match (shared_state_lock.sync_request, self.frame_counter) {
+            (SyncRequest::Configured(value, false), _) => {
+                shared_state_lock.sync_request = SyncRequest::None;
+                match self.sync_counter {
+                    Some(counter) => (counter, value),
+                    None => return,
+                }
+            }
+            (sync_request, Some(counter)) => {
+                let counter_value = shared_state_lock.frame.counter_value;
+                let value = match sync_request {
+                    SyncRequest::Configured(value, true) => {
+                        shared_state_lock.sync_request = SyncRequest::None;
+                        value.max(counter_value + 1)
+                    }
+                    _ if counter_value % 2 == 1 => counter_value + 1,
+                    // Not in a frame.
+                    _ => return,
+                };
+
+                let frame = &mut shared_state_lock.frame;
+                frame.counter_value = value + value % 2;
+                frame.pending = Some(frame.counter_value);
+                (counter, frame.counter_value)
+            }
+            _ => return,
+        };
+        drop(shared_state_lock);
+
+        self.set_sync_counter(counter, value);
+    }
+
+    /// Start a frame before the window is redrawn, by making the extended counter odd.
+    pub(super) fn begin_frame(&self) {
+        let counter = match self.frame_counter {
+            Some(counter) => counter,
+            None => return,
+        };
+
+        let mut shared_state_lock = self.shared_state_lock();
+        let frame = &mut shared_state_lock.frame;
+        if frame.counter_value % 2 == 0 {
+            frame.counter_value += 1;
+            let value = frame.counter_value;
+            drop(shared_state_lock);
+            self.set_sync_counter(counter, value);
+        }
+    }
+
+    /// The compositor has drawn the frame with the given counter value at the given time.
+    ///
+    /// The redraw requested with `request_frame` is sent, unless the next frame is being drawn.
+    pub(super) fn frame_drawn(&self, value: i64, time: Duration) {
+        let mut shared_state_lock = self.shared_state_lock();
+        let frame = &mut shared_state_lock.frame;
+        if frame.pending.is_some_and(|pending| value >= pending) {
+            frame.pending = None;
+        }
+
+        if frame.pending.is_none() && frame.counter_value % 2 == 0 && frame.requested {
+            frame.requested = false;
+            frame.time = Some(time);
+            drop(shared_state_lock);
+            self.request_redraw();
+        }
+    }
+
+    /// The time the frame was drawn, when the redraw was requested with `request_frame`.
+    pub(super) fn take_frame_time(&self) -> Option<Duration> {
+        self.shared_state_lock().frame.time.take()
+    }
+
+    fn set_sync_counter(&self, counter: sync::Counter, value: i64) {
+        let value = sync::Int64 {
+            hi: (value >> 32) as i32,
+            lo: value as u32,
+        };
+        self.xconn
+            .xcb_connection()
+            .sync_set_counter(counter, value)
+            .expect_then_ignore_error("Failed to update the sync counter");
+        self.xconn
+            .flush_requests()
+            .expect("Failed to update the sync counter");
+    }
+
     fn set_pid(&self) -> Result<Option<VoidCookie<'_>>, X11Error> {
         let atoms = self.xconn.atoms();
         let pid_atom = atoms[_NET_WM_PID];
@@ -669,7 +1031,10 @@ impl UnownedWindow {
         let variant = match theme {
             Some(Theme::Dark) => "dark",
             Some(Theme::Light) => "light",
-            None => "dark",
+            None => match self.xconn.system_theme() {
+                Some(Theme::Light) => "light",
+                Some(Theme::Dark) | None => "dark",
+            },
         };
         let variant = CString::new(variant).expect("`_GTK_THEME_VARIANT` contained null byte");
         self.xconn.change_property(
@@ -683,6 +1048,7 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
+        self.shared_state_lock().theme = theme;
         self.set_theme_inner(theme)
             .expect("Failed to change window theme")
             .ignore_error();
@@ -785,6 +1151,11 @@ impl UnownedWindow {
             _ => (),
         }
 
+        // Restore the gamma ramps changed while the window was fullscreen.
+        if old_fullscreen.is_some() && fullscreen.is_none() {
+            self.xconn.remove_fullscreen_window(self.xwindow, None);
+        }
+
         drop(shared_state_lock);
 
         match fullscreen {
@@ -818,6 +1189,9 @@ impl UnownedWindow {
                     return Ok(None);
                 }
 
+                // The gamma ramps set on the monitor are restored once the window leaves it.
+                self.xconn.set_fullscreen_window(self.xwindow, monitor.id);
+
                 if let Some(video_mode) = video_mode {
                     // FIXME: this is actually not correct if we're setting the
                     // video mode to a resolution higher than the current
@@ -1578,6 +1952,17 @@ impl UnownedWindow {
         }
     }
 
+    /// Load the named cursor again, after the cursor theme changed.
+    pub(crate) fn reload_cursor_icon(&self) {
+        if !*self.cursor_visible.lock().unwrap() {
+            return;
+        }
+
+        if let SelectedCursor::Named(icon) = &*self.selected_cursor.lock().unwrap() {
+            self.xconn.set_cursor_icon(self.xwindow, Some(*icon));
+        }
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
@@ -1730,6 +2115,72 @@ impl UnownedWindow {
         Ok(())
     }
 
+    pub fn inject_key(&self, key: PhysicalKey, state: ElementState) -> Result<(), ExternalError> {
+        let keycode = match crate::platform_impl::common::keymap::physicalkey_to_scancode(key) {
+            Some(scancode) => scancode + 8,
+            None => {
+                return Err(ExternalError::Os(os_error!(OsError::Misc(
+                    "the key has no X11 keycode."
+                ))))
+            }
+        };
+        let event_type = match state {
+            ElementState::Pressed => xproto::KEY_PRESS_EVENT,
+            ElementState::Released => xproto::KEY_RELEASE_EVENT,
+        };
+
+        self.xconn
+            .fake_input(event_type, keycode as u8, x11rb::NONE, 0, 0)
+            .map_err(fake_input_error)
+    }
+
+    pub fn inject_pointer(&self, pointer: InjectedPointer) -> Result<(), ExternalError> {
+        match pointer {
+            InjectedPointer::Moved(position) => {
+                let position = position.to_physical::<i32>(self.scale_factor());
+                let origin = self
+                    .xconn
+                    .translate_coords(self.xwindow, self.root)
+                    .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+                let coordinate = |origin: i16, position: i32| {
+                    i16::try_from(origin as i32 + position).map_err(|_| {
+                        ExternalError::Os(os_error!(OsError::Misc(
+                            "the position is out of the range of the X11 coordinates."
+                        )))
+                    })
+                };
+                let x = coordinate(origin.dst_x, position.x)?;
+                let y = coordinate(origin.dst_y, position.y)?;
+
+                self.xconn
+                    .fake_input(xproto::MOTION_NOTIFY_EVENT, 0, self.root, x, y)
+                    .map_err(fake_input_error)
+            }
+            InjectedPointer::Button { button, state } => {
+                let button = match button {
+                    MouseButton::Left => xproto::ButtonIndex::M1.into(),
+                    MouseButton::Middle => xproto::ButtonIndex::M2.into(),
+                    MouseButton::Right => xproto::ButtonIndex::M3.into(),
+                    MouseButton::Back => 8,
+                    MouseButton::Forward => 9,
+                    MouseButton::Other(button) => u8::try_from(button).map_err(|_| {
+                        ExternalError::Os(os_error!(OsError::Misc(
+                            "the button is out of the range of the X11 buttons."
+                        )))
+                    })?,
+                };
+                let event_type = match state {
+                    ElementState::Pressed => xproto::BUTTON_PRESS_EVENT,
+                    ElementState::Released => xproto::BUTTON_RELEASE_EVENT,
+                };
+
+                self.xconn
+                    .fake_input(event_type, button, x11rb::NONE, 0, 0)
+                    .map_err(fake_input_error)
+            }
+        }
+    }
+
     /// Moves the window while it is being dragged.
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         self.drag_initiate(util::MOVERESIZE_MOVE)
@@ -1826,6 +2277,12 @@ impl UnownedWindow {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     pub fn focus_window(&self) {
         let atoms = self.xconn.atoms();
@@ -1921,9 +2378,32 @@ impl UnownedWindow {
             .unwrap();
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        // Without `_NET_WM_FRAME_DRAWN` the redraws can't be paced.
+        if self.frame_counter.is_none() {
+            return self.request_redraw();
+        }
+
+        let mut shared_state_lock = self.shared_state_lock();
+        let frame = &mut shared_state_lock.frame;
+        frame.requested = true;
+
+        // The redraw is sent once the last frame, or the one being drawn, is drawn.
+        if frame.pending.is_some() || frame.counter_value % 2 == 1 {
+            return;
+        }
+        drop(shared_state_lock);
+
+        // Otherwise, end an empty frame for the compositor to report at its next frame.
+        self.begin_frame();
+        self.update_sync_counter();
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
-        // TODO timer
+        // The frame for the new size is about to be presented.
+        self.update_sync_counter();
     }
 
     #[cfg(feature = "rwh_04")]
@@ -1979,11 +2459,42 @@ impl UnownedWindow {
 
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
-        None
+        let theme = self.shared_state_lock().theme;
+        theme.or_else(|| self.xconn.system_theme())
+    }
+
+    #[inline]
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        self.xconn.xsettings().accent_color()
     }
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    #[inline]
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    #[inline]
+    pub fn set_content_type(&self, content_type: ContentType) {
+        let atoms = self.xconn.atoms();
+        // 0 means no preference, 1 requests to bypass the compositor.
+        let bypass = match content_type {
+            ContentType::Video | ContentType::Game => 1,
+            ContentType::None | ContentType::Photo => 0,
+        };
+        self.xconn
+            .change_property(
+                self.xwindow,
+                atoms[_NET_WM_BYPASS_COMPOSITOR],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[bypass as util::Cardinal],
+            )
+            .expect_then_ignore_error("Failed to set `_NET_WM_BYPASS_COMPOSITOR`");
+        self.xconn
+            .flush_requests()
+            .expect("Failed to set `_NET_WM_BYPASS_COMPOSITOR`");
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.shared_state_lock().has_focus
@@ -2015,3 +2526,13 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+/// The error of an input injected with the XTest extension.
+fn fake_input_error(err: X11Error) -> ExternalError {
+    match err {
+        X11Error::Connection(x11rb::errors::ConnectionError::UnsupportedExtension) => {
+            ExternalError::NotSupported(NotSupportedError::new())
+        }
+        err => ExternalError::Os(os_error!(OsError::XError(err.into()))),
+    }
+}
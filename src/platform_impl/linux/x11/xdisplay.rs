@@ -8,12 +8,19 @@ use std::{
     },
 };
 
-use crate::window::CursorIcon;
+use crate::{
+    monitor::GammaRamp,
+    window::{CursorIcon, Theme},
+};
 
-use super::{atoms::Atoms, ffi, monitor::MonitorHandle};
+use super::{atoms::Atoms, ffi, monitor::MonitorHandle, util::XSettings};
 use x11rb::{
-    connection::Connection,
-    protocol::{randr::ConnectionExt as _, xproto},
+    connection::{Connection, RequestConnection},
+    protocol::{
+        randr::{self, ConnectionExt as _},
+        sync::ConnectionExt as _,
+        xproto,
+    },
     resource_manager,
     xcb_ffi::XCBConnection,
 };
@@ -49,12 +56,31 @@ pub(crate) struct XConnection {
     /// List of monitor handles.
     pub monitor_handles: Mutex<Option<Vec<MonitorHandle>>>,
 
+    /// The gamma ramps of the CRTCs before they were changed, with the fullscreen window they're
+    /// restored for.
+    pub saved_gamma_ramps: Mutex<HashMap<randr::Crtc, (GammaRamp, Option<xproto::Window>)>>,
+
+    /// The fullscreen window on each CRTC, owning the gamma ramps set on it.
+    pub fullscreen_windows: Mutex<HashMap<randr::Crtc, xproto::Window>>,
+
+    /// The window of the XSETTINGS manager.
+    pub xsettings_owner: Mutex<Option<xproto::Window>>,
+
+    /// The settings of the XSETTINGS manager.
+    pub xsettings: Mutex<XSettings>,
+
+    /// The theme of the `color-scheme` of the XDG desktop portal.
+    pub color_scheme: Mutex<Option<Theme>>,
+
     /// The resource database.
     database: RwLock<resource_manager::Database>,
 
     /// RandR version.
     randr_version: (u32, u32),
 
+    /// XSync version, if the extension is available.
+    sync_version: Option<(u32, u32)>,
+
     pub latest_error: Mutex<Option<XError>>,
     pub cursor_cache: Mutex<HashMap<Option<CursorIcon>, ffi::Cursor>>,
 }
@@ -119,6 +145,14 @@ impl XConnection {
             .reply()
             .expect("failed to query XRandR version");
 
+        // Initialize the XSync extension, it must be done once before using it.
+        let sync_version = xcb
+            .extension_information(x11rb::protocol::sync::X11_EXTENSION_NAME)
+            .ok()
+            .flatten()
+            .and_then(|_| xcb.sync_initialize(3, 1).ok()?.reply().ok())
+            .map(|reply| (reply.major_version as u32, reply.minor_version as u32));
+
         Ok(XConnection {
             xlib,
             xcursor,
@@ -130,9 +164,15 @@ impl XConnection {
             timestamp: AtomicU32::new(0),
             latest_error: Mutex::new(None),
             monitor_handles: Mutex::new(None),
+            saved_gamma_ramps: Mutex::new(HashMap::new()),
+            fullscreen_windows: Mutex::new(HashMap::new()),
+            xsettings_owner: Mutex::new(None),
+            xsettings: Mutex::new(XSettings::default()),
+            color_scheme: Mutex::new(None),
             database: RwLock::new(database),
             cursor_cache: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
+            sync_version,
         })
     }
 
@@ -152,6 +192,11 @@ impl XConnection {
         self.randr_version
     }
 
+    #[inline]
+    pub fn sync_version(&self) -> Option<(u32, u32)> {
+        self.sync_version
+    }
+
     /// Get the underlying XCB connection.
     #[inline]
     pub fn xcb_connection(&self) -> &XCBConnection {
@@ -232,6 +277,7 @@ impl fmt::Debug for XConnection {
 impl Drop for XConnection {
     #[inline]
     fn drop(&mut self) {
+        self.restore_gamma_ramps();
         self.xcb = None;
         unsafe { (self.xlib.XCloseDisplay)(self.display) };
     }
@@ -292,7 +292,7 @@ impl ApplicationDelegate {
         if !self.ivars().in_callback.get() {
             self.handle_nonuser_event(Event::WindowEvent {
                 window_id: RootWindowId(window_id),
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { frame_time: None },
             });
             self.ivars().in_callback.set(false);
 
@@ -454,7 +454,7 @@ impl ApplicationDelegate {
         for window_id in redraw {
             self.handle_nonuser_event(Event::WindowEvent {
                 window_id: RootWindowId(window_id),
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { frame_time: None },
             });
         }
 
@@ -28,6 +28,20 @@ pub struct KeyEventExtra {
     pub key_without_modifiers: Key,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfoExtra;
+
+impl KeyboardLayoutInfoExtra {
+    pub fn key_for_physical(
+        &self,
+        _layout: usize,
+        _physical_key: crate::keyboard::PhysicalKey,
+        _modifiers: crate::keyboard::ModifiersState,
+    ) -> crate::keyboard::Key {
+        crate::keyboard::Key::Unidentified(crate::keyboard::NativeKey::Unidentified)
+    }
+}
+
 /// Ignores ALL modifiers.
 pub fn get_modifierless_char(scancode: u16) -> Key {
     let mut string = [0; 16];
@@ -186,6 +200,7 @@ pub(crate) fn create_key_event(
         logical_key,
         physical_key,
         repeat: is_repeat,
+        is_generated: false,
         state,
         text,
         platform_specific: KeyEventExtra {
@@ -395,6 +410,7 @@ pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
 
     Modifiers {
         state,
+        lock_state: Default::default(),
         pressed_mods,
     }
 }
@@ -19,7 +19,7 @@ use core_foundation::runloop::{
 };
 use icrate::AppKit::{
     NSApplication, NSApplicationActivationPolicyAccessory, NSApplicationActivationPolicyProhibited,
-    NSApplicationActivationPolicyRegular, NSWindow,
+    NSApplicationActivationPolicyRegular, NSEvent, NSWindow,
 };
 use icrate::Foundation::{MainThreadMarker, NSObjectProtocol};
 use objc2::{msg_send_id, ClassType};
@@ -40,6 +40,7 @@ use crate::{
     event::Event,
     event_loop::{
         ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget,
+        SystemSettings,
     },
     platform::{macos::ActivationPolicy, pump_events::PumpStatus},
 };
@@ -90,6 +91,36 @@ impl EventLoopWindowTarget {
         Some(monitor)
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> SystemSettings {
+        let (double_click_interval, key_repeat_delay, key_repeat_interval) = unsafe {
+            (
+                NSEvent::doubleClickInterval(),
+                NSEvent::keyRepeatDelay(),
+                NSEvent::keyRepeatInterval(),
+            )
+        };
+
+        SystemSettings {
+            double_click_interval: Some(Duration::from_secs_f64(double_click_interval)),
+            key_repeat_delay: Some(Duration::from_secs_f64(key_repeat_delay)),
+            key_repeat_interval: Some(Duration::from_secs_f64(key_repeat_interval)),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
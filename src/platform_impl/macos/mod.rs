@@ -17,7 +17,9 @@ mod window_delegate;
 use std::fmt;
 
 pub(crate) use self::{
-    event::{physicalkey_to_scancode, scancode_to_physicalkey, KeyEventExtra},
+    event::{
+        physicalkey_to_scancode, scancode_to_physicalkey, KeyEventExtra, KeyboardLayoutInfoExtra,
+    },
     event_loop::{
         EventLoop, EventLoopProxy, EventLoopWindowTarget, OwnedDisplayHandle,
         PlatformSpecificEventLoopAttributes,
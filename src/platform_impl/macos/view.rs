@@ -322,7 +322,7 @@ declare_class!(
             };
 
             // Send WindowEvent for updating marked text
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(preedit_string, cursor_range)));
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(preedit_string, cursor_range, Vec::new())));
         }
 
         #[method(unmarkText)]
@@ -333,7 +333,7 @@ declare_class!(
             let input_context = self.inputContext().expect("input context");
             input_context.discardMarkedText();
 
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
             if self.is_ime_enabled() {
                 // Leave the Preedit self.ivars()
                 self.ivars().ime_state.set(ImeState::Ground);
@@ -395,7 +395,7 @@ declare_class!(
 
             // Commit only if we have marked text.
             if unsafe { self.hasMarkedText() } && self.is_ime_enabled() && !is_control {
-                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
                 self.queue_event(WindowEvent::Ime(Ime::Commit(string)));
                 self.ivars().ime_state.set(ImeState::Commited);
             }
@@ -39,8 +39,8 @@ use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::WindowEvent;
 use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    ContentType, Cursor, CursorGrabMode, Icon, ImeHints, ImePurpose, PresentationHint,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
 };
 
 #[derive(Clone, Debug)]
@@ -845,6 +845,11 @@ impl WindowDelegate {
         app_delegate.queue_redraw(self.window().id());
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {}
 
@@ -1126,6 +1131,23 @@ impl WindowDelegate {
         Ok(())
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporalily.
@@ -1517,6 +1539,12 @@ impl WindowDelegate {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     pub fn focus_window(&self) {
         let mtm = MainThreadMarker::from(self);
@@ -1613,6 +1641,11 @@ impl WindowDelegate {
         self.ivars().current_theme.get()
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.window().isKeyWindow()
@@ -1635,6 +1668,12 @@ impl WindowDelegate {
         })
     }
 
+    #[inline]
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    #[inline]
+    pub fn set_content_type(&self, _content_type: ContentType) {}
+
     pub fn title(&self) -> String {
         self.window().title().to_string()
     }
@@ -267,6 +267,7 @@ impl EventState {
 
         Modifiers {
             state,
+            lock_state: Default::default(),
             pressed_mods,
         }
     }
@@ -351,6 +352,7 @@ impl<T: 'static> EventLoop<T> {
                                 location: KeyLocation::Standard,
                                 state: element_state(pressed),
                                 repeat: false,
+                                is_generated: false,
                                 text: None,
 
                                 platform_specific: KeyEventExtra {},
@@ -371,7 +373,7 @@ impl<T: 'static> EventLoop<T> {
             EventOption::TextInput(TextInputEvent { character }) => {
                 event_handler(event::Event::WindowEvent {
                     window_id: RootWindowId(window_id),
-                    event: event::WindowEvent::Ime(Ime::Preedit("".into(), None)),
+                    event: event::WindowEvent::Ime(Ime::Preedit("".into(), None, Vec::new())),
                 });
                 event_handler(event::Event::WindowEvent {
                     window_id: RootWindowId(window_id),
@@ -595,7 +597,7 @@ impl<T: 'static> EventLoop<T> {
                 event_handler(
                     event::Event::WindowEvent {
                         window_id: RootWindowId(window_id),
-                        event: event::WindowEvent::RedrawRequested,
+                        event: event::WindowEvent::RedrawRequested { frame_time: None },
                     },
                     &self.window_target,
                 );
@@ -737,6 +739,23 @@ impl EventLoopWindowTarget {
         v
     }
 
+    #[inline]
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
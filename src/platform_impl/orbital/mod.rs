@@ -224,6 +224,49 @@ impl MonitorHandle {
         None
     }
 
+    pub fn logical_position(&self) -> crate::dpi::LogicalPosition<i32> {
+        self.position().to_logical(self.scale_factor())
+    }
+
+    pub fn logical_size(&self) -> crate::dpi::LogicalSize<u32> {
+        self.size().to_logical(self.scale_factor())
+    }
+
+    pub fn transform(&self) -> crate::monitor::MonitorTransform {
+        crate::monitor::MonitorTransform::Normal
+    }
+
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    pub fn model(&self) -> Option<String> {
+        None
+    }
+
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    pub fn gamma_ramp(&self) -> Result<crate::monitor::GammaRamp, crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn set_gamma_ramp(
+        &self,
+        _ramp: &crate::monitor::GammaRamp,
+    ) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let size = self.size().into();
         // FIXME this is not the real refresh rate
@@ -265,3 +308,17 @@ impl VideoModeHandle {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyEventExtra {}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyboardLayoutInfoExtra {}
+
+impl KeyboardLayoutInfoExtra {
+    pub fn key_for_physical(
+        &self,
+        _layout: usize,
+        _physical_key: crate::keyboard::PhysicalKey,
+        _modifiers: crate::keyboard::ModifiersState,
+    ) -> crate::keyboard::Key {
+        crate::keyboard::Key::Unidentified(crate::keyboard::NativeKey::Unidentified)
+    }
+}
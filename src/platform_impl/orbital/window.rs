@@ -9,7 +9,7 @@ use crate::{
     error,
     platform_impl::Fullscreen,
     window,
-    window::ImePurpose,
+    window::{ImeHints, ImePurpose},
 };
 
 use super::{
@@ -169,6 +169,11 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {}
 
@@ -342,6 +347,12 @@ impl Window {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     pub fn focus_window(&self) {}
 
@@ -395,6 +406,27 @@ impl Window {
         ))
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     #[cfg(feature = "rwh_04")]
     #[inline]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
@@ -450,6 +482,11 @@ impl Window {
         None
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         false
@@ -459,6 +496,10 @@ impl Window {
     pub fn set_theme(&self, _theme: Option<window::Theme>) {}
 
     pub fn set_content_protected(&self, _protected: bool) {}
+
+    pub fn set_presentation_hint(&self, _hint: window::PresentationHint) {}
+
+    pub fn set_content_type(&self, _content_type: window::ContentType) {}
 }
 
 impl Drop for Window {
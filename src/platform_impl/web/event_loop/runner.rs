@@ -586,7 +586,7 @@ impl Shared {
         for window_id in redraw_events {
             self.handle_event(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { frame_time: None },
             });
         }
 
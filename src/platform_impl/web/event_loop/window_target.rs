@@ -153,6 +153,7 @@ impl EventLoopWindowTarget {
                                 location,
                                 state: ElementState::Pressed,
                                 repeat,
+                                is_generated: false,
                                 platform_specific: KeyEventExtra,
                             },
                             is_synthetic: false,
@@ -189,6 +190,7 @@ impl EventLoopWindowTarget {
                                 location,
                                 state: ElementState::Released,
                                 repeat,
+                                is_generated: false,
                                 platform_specific: KeyEventExtra,
                             },
                             is_synthetic: false,
@@ -673,6 +675,22 @@ impl EventLoopWindowTarget {
         ))
     }
 
+    pub fn system_settings(&self) -> crate::event_loop::SystemSettings {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         self.runner.listen_device_events(allowed)
     }
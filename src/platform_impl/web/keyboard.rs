@@ -5,6 +5,20 @@ use crate::keyboard::{Key, KeyCode, NamedKey, NativeKey, NativeKeyCode, Physical
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub(crate) struct KeyEventExtra;
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct KeyboardLayoutInfoExtra;
+
+impl KeyboardLayoutInfoExtra {
+    pub(crate) fn key_for_physical(
+        &self,
+        _layout: usize,
+        _physical_key: crate::keyboard::PhysicalKey,
+        _modifiers: crate::keyboard::ModifiersState,
+    ) -> crate::keyboard::Key {
+        crate::keyboard::Key::Unidentified(crate::keyboard::NativeKey::Unidentified)
+    }
+}
+
 impl Key {
     pub(crate) fn from_key_attribute_value(kav: &str) -> Self {
         Key::Named(match kav {
@@ -39,7 +39,7 @@ pub(crate) use self::event_loop::{
 pub use self::monitor::{MonitorHandle, VideoModeHandle};
 pub use self::window::{PlatformSpecificWindowBuilderAttributes, Window, WindowId};
 
-pub(crate) use self::keyboard::KeyEventExtra;
+pub(crate) use self::keyboard::{KeyEventExtra, KeyboardLayoutInfoExtra};
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
 pub(crate) use crate::platform_impl::Fullscreen;
 pub(crate) use cursor::CustomCursor as PlatformCustomCursor;
@@ -26,6 +26,45 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn logical_position(&self) -> crate::dpi::LogicalPosition<i32> {
+        unreachable!()
+    }
+
+    pub fn logical_size(&self) -> crate::dpi::LogicalSize<u32> {
+        unreachable!()
+    }
+
+    pub fn transform(&self) -> crate::monitor::MonitorTransform {
+        unreachable!()
+    }
+
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        unreachable!()
+    }
+
+    pub fn manufacturer(&self) -> Option<String> {
+        unreachable!()
+    }
+
+    pub fn model(&self) -> Option<String> {
+        unreachable!()
+    }
+
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        unreachable!()
+    }
+
+    pub fn gamma_ramp(&self) -> Result<crate::monitor::GammaRamp, crate::error::ExternalError> {
+        unreachable!()
+    }
+
+    pub fn set_gamma_ramp(
+        &self,
+        _ramp: &crate::monitor::GammaRamp,
+    ) -> Result<(), crate::error::ExternalError> {
+        unreachable!()
+    }
+
     pub fn video_modes(&self) -> Empty<VideoModeHandle> {
         unreachable!()
     }
@@ -2,8 +2,8 @@ use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
 use crate::icon::Icon;
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
+    ContentType, Cursor, CursorGrabMode, ImeHints, ImePurpose, PresentationHint, ResizeDirection,
+    Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
 };
 
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
@@ -143,6 +143,10 @@ impl Inner {
         self.canvas.borrow().request_animation_frame();
     }
 
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     pub fn pre_present_notify(&self) {}
 
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
@@ -279,6 +283,23 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_minimized(&self, _minimized: bool) {
         // Intentionally a no-op, as canvases cannot be 'minimized'
@@ -355,6 +376,16 @@ impl Inner {
         // Currently not implemented
     }
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {
+        // Currently not implemented
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {
+        // Currently not implemented
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let _ = self.canvas.borrow().raw().focus();
@@ -421,8 +452,17 @@ impl Inner {
         })
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    pub fn set_content_type(&self, _content_type: ContentType) {}
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.canvas.borrow().has_focus.get()
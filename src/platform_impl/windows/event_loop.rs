@@ -74,7 +74,10 @@ use crate::{
         DeviceEvent, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, Touch, TouchPhase,
         WindowEvent,
     },
-    event_loop::{ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    event_loop::{
+        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW,
+        SystemSettings,
+    },
     keyboard::ModifiersState,
     platform::pump_events::PumpStatus,
     platform_impl::platform::{
@@ -140,6 +143,8 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: EventLoopRunnerShared<UserEventPlaceholder>,
+    /// The last system settings, to report their changes.
+    system_settings: Cell<SystemSettings>,
 }
 
 impl ThreadMsgTargetData {
@@ -560,6 +565,22 @@ impl EventLoopWindowTarget {
         ))
     }
 
+    pub fn system_settings(&self) -> SystemSettings {
+        util::system_settings()
+    }
+
+    #[inline]
+    pub fn keyboard_layouts(&self) -> crate::keyboard::KeyboardLayoutInfo {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn set_keyboard_layout(&self, _index: usize) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
     }
@@ -916,7 +937,10 @@ fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: EventLoopRunnerShared<UserEventPlaceholder>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    let userdata = ThreadMsgTargetData {
+        event_loop_runner,
+        system_settings: Cell::new(util::system_settings()),
+    };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { super::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -1210,7 +1234,7 @@ unsafe fn public_window_callback_inner(
             if !userdata.event_loop_runner.should_buffer() {
                 userdata.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(window)),
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { frame_time: None },
                 });
             }
 
@@ -1376,7 +1400,7 @@ unsafe fn public_window_callback_inner(
                 if lparam == 0 {
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
-                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     });
                 }
 
@@ -1388,7 +1412,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         });
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
@@ -1407,7 +1431,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(text, cursor_range)),
+                            event: WindowEvent::Ime(Ime::Preedit(text, cursor_range, Vec::new())),
                         });
                     }
                 }
@@ -1430,7 +1454,7 @@ unsafe fn public_window_callback_inner(
                     if let Some(text) = unsafe { ime_context.get_composed_text() } {
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         });
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
@@ -2424,6 +2448,16 @@ unsafe extern "system" fn thread_event_target_callback(
             unsafe { DefWindowProcW(window, msg, wparam, lparam) }
         }
 
+        // The top-level windows get the message, the thread's target reports it only once.
+        WM_SETTINGCHANGE => {
+            let settings = util::system_settings();
+            if userdata.system_settings.replace(settings) != settings {
+                userdata.send_event(Event::SystemSettingsChanged(settings));
+            }
+
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        }
+
         _ if msg == USER_EVENT_MSG_ID.get() => {
             // synthesis a placeholder UserEvent, so that if the callback is
             // re-entered it can be buffered for later delivery. the real
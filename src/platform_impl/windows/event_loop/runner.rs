@@ -217,7 +217,7 @@ impl<T> EventLoopRunner<T> {
 
     pub(crate) fn send_event(&self, event: Event<T>) {
         if let Event::WindowEvent {
-            event: WindowEvent::RedrawRequested,
+            event: WindowEvent::RedrawRequested { .. },
             ..
         } = event
         {
@@ -225,6 +225,58 @@ impl MonitorHandle {
         dpi_to_scale_factor(get_monitor_dpi(self.0).unwrap_or(96))
     }
 
+    #[inline]
+    pub fn logical_position(&self) -> crate::dpi::LogicalPosition<i32> {
+        self.position().to_logical(self.scale_factor())
+    }
+
+    #[inline]
+    pub fn logical_size(&self) -> crate::dpi::LogicalSize<u32> {
+        self.size().to_logical(self.scale_factor())
+    }
+
+    #[inline]
+    pub fn transform(&self) -> crate::monitor::MonitorTransform {
+        crate::monitor::MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn edid(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    #[inline]
+    pub fn gamma_ramp(&self) -> Result<crate::monitor::GammaRamp, crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(
+        &self,
+        _ramp: &crate::monitor::GammaRamp,
+    ) -> Result<(), crate::error::ExternalError> {
+        Err(crate::error::ExternalError::NotSupported(
+            crate::error::NotSupportedError::new(),
+        ))
+    }
+
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         // EnumDisplaySettingsExW can return duplicate values (or some of the
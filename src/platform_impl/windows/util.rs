@@ -7,6 +7,7 @@ use std::{
     os::windows::prelude::{OsStrExt, OsStringExt},
     ptr,
     sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
 
 use once_cell::sync::Lazy;
@@ -18,25 +19,29 @@ use windows_sys::{
         System::{
             LibraryLoader::{GetProcAddress, LoadLibraryA},
             SystemServices::IMAGE_DOS_HEADER,
+            Threading::INFINITE,
         },
         UI::{
             HiDpi::{DPI_AWARENESS_CONTEXT, MONITOR_DPI_TYPE, PROCESS_DPI_AWARENESS},
             Input::{
-                KeyboardAndMouse::GetActiveWindow,
+                KeyboardAndMouse::{GetActiveWindow, GetDoubleClickTime},
                 Pointer::{POINTER_INFO, POINTER_PEN_INFO, POINTER_TOUCH_INFO},
             },
             WindowsAndMessaging::{
-                ClipCursor, GetClientRect, GetClipCursor, GetSystemMetrics, GetWindowPlacement,
-                GetWindowRect, IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS,
-                IDC_HAND, IDC_HELP, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS,
-                IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
-                SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_MAXIMIZE, WINDOWPLACEMENT,
+                ClipCursor, GetCaretBlinkTime, GetClientRect, GetClipCursor, GetSystemMetrics,
+                GetWindowPlacement, GetWindowRect, IsIconic, ShowCursor, SystemParametersInfoW,
+                IDC_APPSTARTING, IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM, IDC_NO,
+                IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT,
+                SM_CXDOUBLECLK, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+                SM_YVIRTUALSCREEN, SPI_GETCLIENTAREAANIMATION, SPI_GETKEYBOARDDELAY,
+                SPI_GETKEYBOARDSPEED, SPI_GETWHEELSCROLLLINES, SW_MAXIMIZE,
+                SYSTEM_PARAMETERS_INFO_ACTION, WINDOWPLACEMENT,
             },
         },
     },
 };
 
-use crate::window::CursorIcon;
+use crate::{event_loop::SystemSettings, window::CursorIcon};
 
 pub fn encode_wide(string: impl AsRef<OsStr>) -> Vec<u16> {
     string.as_ref().encode_wide().chain(once(0)).collect()
@@ -144,6 +149,40 @@ pub fn get_desktop_rect() -> RECT {
     }
 }
 
+pub(crate) fn system_settings() -> SystemSettings {
+    fn parameter(action: SYSTEM_PARAMETERS_INFO_ACTION) -> Option<u32> {
+        let mut value = 0u32;
+        let ok = unsafe { SystemParametersInfoW(action, 0, &mut value as *mut _ as _, 0) };
+        (ok != false.into()).then_some(value)
+    }
+
+    // The delay is from 0 (250ms) to 3 (1s), and the speed from 0 (2.5 repeats per second) to
+    // 31 (30 repeats per second).
+    let key_repeat_delay = parameter(SPI_GETKEYBOARDDELAY)
+        .map(|delay| Duration::from_millis((delay as u64 + 1) * 250));
+    let key_repeat_interval = parameter(SPI_GETKEYBOARDSPEED)
+        .map(|speed| Duration::from_secs_f64(1. / (2.5 + speed as f64 * 27.5 / 31.)));
+
+    let caret_blink_interval = match unsafe { GetCaretBlinkTime() } {
+        0 => None,
+        INFINITE => Some(Duration::ZERO),
+        time => Some(Duration::from_millis(time as u64)),
+    };
+
+    // The width of the rectangle centered on the first click.
+    let double_click_distance = unsafe { GetSystemMetrics(SM_CXDOUBLECLK) } / 2;
+
+    SystemSettings {
+        double_click_interval: Some(Duration::from_millis(unsafe { GetDoubleClickTime() } as u64)),
+        double_click_distance: (double_click_distance > 0).then_some(double_click_distance as u32),
+        key_repeat_delay,
+        key_repeat_interval,
+        caret_blink_interval,
+        scroll_lines: parameter(SPI_GETWHEELSCROLLLINES),
+        reduce_motion: parameter(SPI_GETCLIENTAREAANIMATION).map(|animation| animation == 0),
+    }
+}
+
 pub fn is_focused(window: HWND) -> bool {
     window == unsafe { GetActiveWindow() }
 }
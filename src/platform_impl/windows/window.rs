@@ -83,8 +83,8 @@ use crate::{
         Fullscreen, SelectedCursor, WindowId,
     },
     window::{
-        CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-        WindowButtons, WindowLevel,
+        ContentType, CursorGrabMode, ImeHints, ImePurpose, PresentationHint, ResizeDirection,
+        Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
     },
 };
 
@@ -172,6 +172,11 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_frame(&self) {
+        self.request_redraw()
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {}
 
@@ -650,6 +655,23 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn inject_key(
+        &self,
+        _key: crate::keyboard::PhysicalKey,
+        _state: crate::event::ElementState,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn inject_pointer(
+        &self,
+        _pointer: crate::window::InjectedPointer,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.hwnd())
@@ -950,6 +972,12 @@ impl Window {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
@@ -987,6 +1015,11 @@ impl Window {
         Some(self.window_state_lock().current_theme)
     }
 
+    #[inline]
+    pub fn accent_color(&self) -> Option<crate::window::AccentColor> {
+        None
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         let window_state = self.window_state.lock().unwrap();
@@ -1046,6 +1079,12 @@ impl Window {
         };
     }
 
+    #[inline]
+    pub fn set_presentation_hint(&self, _hint: PresentationHint) {}
+
+    #[inline]
+    pub fn set_content_type(&self, _content_type: ContentType) {}
+
     #[inline]
     pub fn reset_dead_keys(&self) {
         // `ToUnicode` consumes the dead-key by default, so we are constructing a fake (but valid)
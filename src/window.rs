@@ -4,7 +4,9 @@ use std::fmt;
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError},
+    event::{ElementState, MouseButton},
     event_loop::EventLoopWindowTarget,
+    keyboard::PhysicalKey,
     monitor::{MonitorHandle, VideoModeHandle},
     platform_impl::{self, PlatformSpecificWindowBuilderAttributes},
 };
@@ -164,6 +166,7 @@ pub struct WindowAttributes {
     #[cfg(feature = "rwh_06")]
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
     pub fullscreen: Option<Fullscreen>,
+    pub(crate) popup: Option<PopupAttributes>,
     // Platform-specific configuration.
     #[allow(dead_code)]
     pub(crate) platform_specific: PlatformSpecificWindowBuilderAttributes,
@@ -195,6 +198,7 @@ impl Default for WindowAttributes {
             #[cfg(feature = "rwh_06")]
             parent_window: None,
             active: true,
+            popup: None,
             platform_specific: Default::default(),
         }
     }
@@ -495,6 +499,64 @@ impl WindowBuilder {
         self
     }
 
+    /// Build a popup window, like a menu, a tooltip or a combo box, attached to `parent`.
+    ///
+    /// The popup is placed relative to the `anchor_rect`, given in the coordinates of the
+    /// `parent` client area. The `anchor` selects the point of the rectangle the popup is
+    /// attached to, and the `gravity` the direction the popup extends to from that point. When
+    /// the popup doesn't fit on the screen, it's moved according to the `constraint_adjustment`.
+    ///
+    /// The size of the popup is taken from [`WindowBuilder::with_inner_size`], while the
+    /// [`WindowBuilder::with_position`] is ignored. The windowing system may dismiss the popup at
+    /// any time, which is reported with [`WindowEvent::PopupDone`], the window should be dropped
+    /// then.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Creates an `xdg_popup` positioned with the `xdg_positioner`.
+    /// - **X11:** Creates an override-redirect window transient for the `parent`.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported, a regular window is
+    ///   created.
+    ///
+    /// [`WindowEvent::PopupDone`]: crate::event::WindowEvent::PopupDone
+    #[inline]
+    pub fn with_popup(
+        mut self,
+        parent: &Window,
+        anchor_rect: (Position, Size),
+        anchor: PopupAnchor,
+        gravity: PopupGravity,
+        constraint_adjustment: ConstraintAdjustment,
+    ) -> Self {
+        self.window.popup = Some(PopupAttributes {
+            parent: parent.id(),
+            anchor_rect,
+            anchor,
+            gravity,
+            constraint_adjustment,
+            grab: false,
+        });
+        self
+    }
+
+    /// Whether the popup should grab the keyboard and the pointer, like menus do.
+    ///
+    /// With the grab, the popup receives all the input and is dismissed with
+    /// [`WindowEvent::PopupDone`] once the user clicks outside of it. The grab must be taken in
+    /// response to a user action on the parent window, like a mouse button press, and is ignored
+    /// for windows not built with [`WindowBuilder::with_popup`].
+    ///
+    /// The default is `false`.
+    ///
+    /// [`WindowEvent::PopupDone`]: crate::event::WindowEvent::PopupDone
+    #[inline]
+    pub fn with_popup_grab(mut self, grab: bool) -> Self {
+        if let Some(popup) = self.window.popup.as_mut() {
+            popup.grab = grab;
+        }
+        self
+    }
+
     /// Modifies the cursor icon of the window.
     ///
     /// The default is [`CursorIcon::Default`].
@@ -631,6 +693,28 @@ impl Window {
         self.window.maybe_queue_on_main(|w| w.request_redraw())
     }
 
+    /// Queues a [`WindowEvent::RedrawRequested`] event aligned with the next frame of the
+    /// windowing system.
+    ///
+    /// Unlike [`Window::request_redraw`], the event is delivered once the compositor signals that
+    /// it's a good time to start drawing a new frame, and the event carries the presentation
+    /// timestamp in its `frame_time` field when the windowing system provides one. This is
+    /// useful for animations, which should be advanced by the time between the frames.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requests a `wl_surface.frame` callback and commits the surface.
+    /// - **X11:** Waits for the compositor to report the last frame with `_NET_WM_FRAME_DRAWN`,
+    ///   ending an empty frame if there's none. Same as [`Window::request_redraw`] when the
+    ///   compositor doesn't support it.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Same as [`Window::request_redraw`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[inline]
+    pub fn request_frame(&self) {
+        self.window.maybe_queue_on_main(|w| w.request_frame())
+    }
+
     /// Notify the windowing system before presenting to the window.
     ///
     /// You should call this event after your drawing operations, but before you submit
@@ -1105,7 +1189,9 @@ impl Window {
     ///
     ///   The dock and the menu bar are disabled in exclusive fullscreen mode.
     /// - **iOS:** Can only be called on the main thread.
-    /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request.
+    /// - **Wayland:** [`Fullscreen::Exclusive`] doesn't change the video mode, but emulates it by
+    ///   scaling the contents of the mode's size to the output with `wp_viewporter`, falling back
+    ///   to the borderless fullscreen when it's unavailable.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
     /// - **Android / Orbital:** Unsupported.
     /// - **Web:** Does nothing without a [transient activation].
@@ -1123,7 +1209,8 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread.
     /// - **Android / Orbital:** Will always return `None`.
-    /// - **Wayland:** Can return `Borderless(None)` when there are no monitors.
+    /// - **Wayland:** Can return `Borderless(None)` when there are no monitors, and returns the
+    ///   emulated video mode for [`Fullscreen::Exclusive`].
     /// - **Web:** Can only return `None` or `Borderless(None)`.
     #[inline]
     pub fn fullscreen(&self) -> Option<Fullscreen> {
@@ -1176,13 +1263,16 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / macOS / Orbital:** Unsupported.
+    /// - **iOS / Android / Web / macOS / Orbital:** Unsupported.
     ///
     /// - **Windows:** Sets `ICON_SMALL`. The base size for a window icon is 16x16, but it's
     ///   recommended to account for screen scaling and pick a multiple of that, i.e. 32x32.
     ///
     /// - **X11:** Has no universal guidelines for icon sizes, so you're at the whims of the WM. That
     ///   said, it's usually in the same ballpark as on Windows.
+    ///
+    /// - **Wayland:** Requires the `xdg_toplevel_icon_manager_v1` protocol. The icon is scaled to
+    ///   the sizes preferred by the compositor, so it's best to provide a large, square, icon.
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         self.window
@@ -1269,6 +1359,39 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_ime_purpose(purpose))
     }
 
+    /// Sets the IME hints for the window using [`ImeHints`].
+    ///
+    /// The hints are combined with the purpose set with [`Window::set_ime_purpose`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_ime_hints(hints))
+    }
+
+    /// Sets the text around the cursor, which the IME uses as the context of the input.
+    ///
+    /// `cursor` and `anchor` are byte offsets into `text`, they are equal when there's no
+    /// selection. The text should be set again whenever it or the cursor changes, including after
+    /// handling [`Ime::Commit`] and [`Ime::DeleteSurrounding`], so the IME can correct and
+    /// recompose the text before the cursor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Text longer than 4000 bytes is truncated around the cursor.
+    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    ///
+    /// [`Ime::Commit`]: crate::event::Ime::Commit
+    /// [`Ime::DeleteSurrounding`]: crate::event::Ime::DeleteSurrounding
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        self.window
+            .maybe_wait_on_main(|w| w.set_ime_surrounding_text(text, cursor, anchor))
+    }
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1320,7 +1443,8 @@ impl Window {
     /// - **macOS:** This is an app-wide setting.
     /// - **Wayland:** Sets the theme for the client side decorations. Using `None` will use dbus
     ///   to get the system preference.
-    /// - **X11:** Sets `_GTK_THEME_VARIANT` hint to `dark` or `light` and if `None` is used, it will default to  [`Theme::Dark`].
+    /// - **X11:** Sets `_GTK_THEME_VARIANT` hint to `dark` or `light` and if `None` is used, it will
+    ///   follow the system theme, or default to [`Theme::Dark`] when it's unknown.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
@@ -1332,12 +1456,28 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **macOS:** This is an app-wide setting.
-    /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
+    /// - **X11:** The theme set with [`Window::set_theme`], or the `color-scheme` of the XDG
+    ///   desktop portal, falling back to the `Net/ThemeName` XSETTINGS.
+    /// - **Wayland:** The theme set with [`Window::set_theme`], or the `color-scheme` of the XDG
+    ///   desktop portal.
+    /// - **iOS / Android / Orbital:** Unsupported.
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
         self.window.maybe_wait_on_main(|w| w.theme())
     }
 
+    /// Returns the accent color of the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The `selected_bg_color` of the `Gtk/ColorScheme` XSETTINGS.
+    /// - **Wayland:** The `accent-color` of the XDG desktop portal.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn accent_color(&self) -> Option<AccentColor> {
+        self.window.maybe_wait_on_main(|w| w.accent_color())
+    }
+
     /// Prevents the window contents from being captured by other apps.
     ///
     /// ## Platform-specific
@@ -1352,6 +1492,39 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_content_protected(protected))
     }
 
+    /// Sets how the windowing system should present the window contents.
+    ///
+    /// See [`PresentationHint`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Uses the `wp_tearing_control_v1` protocol and applies on the next
+    ///   surface commit.
+    /// - **iOS / Android / X11 / Web / Windows / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_presentation_hint(&self, hint: PresentationHint) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_presentation_hint(hint))
+    }
+
+    /// Sets the kind of content the window is displaying.
+    ///
+    /// The windowing system may use it to tune its behavior, for example to lower the latency
+    /// for games or to avoid altering the colors of video.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Uses the `wp_content_type_v1` protocol and applies on the next
+    ///   surface commit.
+    /// - **X11:** [`ContentType::Game`] and [`ContentType::Video`] request to bypass the
+    ///   compositor with `_NET_WM_BYPASS_COMPOSITOR`.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_content_type(&self, content_type: ContentType) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_content_type(content_type))
+    }
+
     /// Gets the current title of the window.
     ///
     /// ## Platform-specific
@@ -1508,6 +1681,43 @@ impl Window {
         self.window
             .maybe_wait_on_main(|w| w.set_cursor_hittest(hittest))
     }
+
+    /// Injects a key press or release, as if it came from a keyboard.
+    ///
+    /// The key goes through the input path of the system, so it's delivered to the focused
+    /// window, which isn't necessarily this one, and is translated with the active keyboard layout.
+    /// This is meant for the end-to-end tests of the applications.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses the XTest extension.
+    /// - **Wayland:** Requires the `zwp_virtual_keyboard_manager_v1` protocol, which is usually
+    ///   only offered to the privileged clients.
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Always returns an
+    ///   [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn inject_key(&self, key: PhysicalKey, state: ElementState) -> Result<(), ExternalError> {
+        self.window.maybe_wait_on_main(|w| w.inject_key(key, state))
+    }
+
+    /// Injects a pointer motion or a button press or release, as if it came from a mouse.
+    ///
+    /// Like [`Window::inject_key`], the input goes through the input path of the system, so the
+    /// buttons are delivered to the window under the pointer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses the XTest extension.
+    /// - **Wayland:** Requires the `zwlr_virtual_pointer_manager_v1` protocol. The clients don't
+    ///   know the position of their windows, so [`InjectedPointer::Moved`] always returns an
+    ///   [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Web / Windows / macOS / Orbital:** Always returns an
+    ///   [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn inject_pointer(&self, pointer: InjectedPointer) -> Result<(), ExternalError> {
+        self.window
+            .maybe_wait_on_main(|w| w.inject_pointer(pointer))
+    }
 }
 
 /// Monitor info functions.
@@ -1678,6 +1888,20 @@ impl From<ResizeDirection> for CursorIcon {
     }
 }
 
+/// A pointer input injected with [`Window::inject_pointer`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InjectedPointer {
+    /// Moves the pointer to the position, relative to the top-left corner of the window.
+    Moved(Position),
+
+    /// Presses or releases the button.
+    Button {
+        button: MouseButton,
+        state: ElementState,
+    },
+}
+
 /// Fullscreen modes.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Fullscreen {
@@ -1698,6 +1922,15 @@ pub enum Theme {
     Dark,
 }
 
+/// The accent color of the system, in sRGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccentColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
 /// ## Platform-specific
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between [`Critical`] and [`Informational`].
@@ -1752,6 +1985,108 @@ pub enum WindowLevel {
     AlwaysOnTop,
 }
 
+/// The way the window contents are presented, used in [`Window::set_presentation_hint`].
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PresentationHint {
+    /// The contents are presented in sync with the vertical blanking period (the default).
+    #[default]
+    Vsync,
+
+    /// The contents are presented as soon as possible, which may cause tearing.
+    ///
+    /// This is useful to reduce the latency, for example in games.
+    Async,
+}
+
+/// The kind of content displayed by the window, used in [`Window::set_content_type`].
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ContentType {
+    /// No specific content (the default).
+    #[default]
+    None,
+
+    /// Digital still pictures.
+    Photo,
+
+    /// Video or animation.
+    Video,
+
+    /// An interactive game.
+    Game,
+}
+
+/// The attributes of the popup window, see [`WindowBuilder::with_popup`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PopupAttributes {
+    pub(crate) parent: WindowId,
+    pub(crate) anchor_rect: (Position, Size),
+    pub(crate) anchor: PopupAnchor,
+    pub(crate) gravity: PopupGravity,
+    pub(crate) constraint_adjustment: ConstraintAdjustment,
+    pub(crate) grab: bool,
+}
+
+/// The point of the anchor rectangle the popup is attached to.
+///
+/// See [`WindowBuilder::with_popup`] for details.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PopupAnchor {
+    /// The center of the rectangle.
+    #[default]
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    BottomLeft,
+    TopRight,
+    BottomRight,
+}
+
+/// The direction the popup extends to from its anchor point.
+///
+/// For example, [`PopupGravity::BottomRight`] places the top left corner of the popup at the
+/// anchor point, and [`PopupGravity::None`] centers the popup on it.
+///
+/// See [`WindowBuilder::with_popup`] for details.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PopupGravity {
+    /// The popup is centered on the anchor point.
+    #[default]
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    BottomLeft,
+    TopRight,
+    BottomRight,
+}
+
+bitflags::bitflags! {
+    /// How the popup is moved when it doesn't fit on the screen.
+    ///
+    /// The adjustments are tried in order: flip, slide and then resize, on each axis
+    /// separately. When none of them applies, the popup may be partially off screen.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ConstraintAdjustment: u32 {
+        /// Move the popup along the X axis until it fits.
+        const SLIDE_X = 1 << 0;
+        /// Move the popup along the Y axis until it fits.
+        const SLIDE_Y = 1 << 1;
+        /// Invert the anchor and the gravity on the X axis.
+        const FLIP_X = 1 << 2;
+        /// Invert the anchor and the gravity on the Y axis.
+        const FLIP_Y = 1 << 3;
+        /// Shrink the popup horizontally to the visible area.
+        const RESIZE_X = 1 << 4;
+        /// Shrink the popup vertically to the visible area.
+        const RESIZE_Y = 1 << 5;
+    }
+}
+
 /// Generic IME purposes for use in [`Window::set_ime_purpose`].
 ///
 /// The purpose may improve UX by optimizing the IME for the specific use case,
@@ -1771,6 +2106,62 @@ pub enum ImePurpose {
     ///
     /// For example, that could alter OSK on Wayland to show extra buttons.
     Terminal,
+    /// The IME is used to input only alphabetic characters.
+    Alpha,
+    /// The IME is used to input only digits.
+    Digits,
+    /// The IME is used to input a number, including the decimal separator and the sign.
+    Number,
+    /// The IME is used to input a phone number.
+    Phone,
+    /// The IME is used to input an URL.
+    Url,
+    /// The IME is used to input an email address.
+    Email,
+    /// The IME is used to input the name of a person.
+    Name,
+    /// The IME is used to input a numeric password.
+    Pin,
+    /// The IME is used to input a date.
+    Date,
+    /// The IME is used to input a time.
+    Time,
+    /// The IME is used to input a date and a time.
+    DateTime,
+}
+
+bitflags::bitflags! {
+    /// Hints about the expected input for use in [`Window::set_ime_hints`].
+    ///
+    /// Like [`ImePurpose`], the hints may improve UX by adapting the IME, when the platform
+    /// supports them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ImeHints: u32 {
+        /// Suggest the completions of the words.
+        const COMPLETION = 1 << 0;
+        /// Suggest the corrections of the words.
+        const SPELLCHECK = 1 << 1;
+        /// Switch to uppercase letters at the start of a sentence.
+        const AUTO_CAPITALIZATION = 1 << 2;
+        /// Prefer lowercase letters.
+        const LOWERCASE = 1 << 3;
+        /// Prefer uppercase letters.
+        const UPPERCASE = 1 << 4;
+        /// Prefer the casing of titles and headings.
+        const TITLECASE = 1 << 5;
+        /// The characters should be hidden.
+        const HIDDEN_TEXT = 1 << 6;
+        /// The typed text should not be stored, e.g. for learning.
+        const SENSITIVE_DATA = 1 << 7;
+        /// Only Latin characters should be entered.
+        const LATIN = 1 << 8;
+        /// The text input is multiline.
+        const MULTILINE = 1 << 9;
+    }
 }
 
 impl Default for ImePurpose {
@@ -0,0 +1,71 @@
+//! End-to-end test of the global hotkeys, with the keys injected through XTest.
+//!
+//! Requires an X server with the XTest extension, so it's ignored by default. Run it with
+//! `xvfb-run cargo test --test x11_global_hotkey -- --ignored`.
+#![cfg(x11_platform)]
+
+use std::time::{Duration, Instant};
+
+use winit::event::{DeviceEvent, ElementState, Event};
+use winit::event_loop::EventLoopBuilder;
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use winit::platform::pump_events::EventLoopExtPumpEvents;
+use winit::platform::x11::{EventLoopBuilderExtX11, EventLoopWindowTargetExtX11};
+use winit::window::WindowBuilder;
+
+#[test]
+#[ignore = "requires an X server, run it with `xvfb-run cargo test -- --ignored`"]
+fn global_hotkey() {
+    let mut event_loop = EventLoopBuilder::new()
+        .with_x11()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+    let window = WindowBuilder::new()
+        .with_visible(false)
+        .build(&event_loop)
+        .unwrap();
+
+    let control = PhysicalKey::Code(KeyCode::ControlLeft);
+    let f12 = PhysicalKey::Code(KeyCode::F12);
+    let hotkey = event_loop
+        .register_global_hotkey(ModifiersState::CONTROL, f12)
+        .unwrap();
+
+    // The hotkey is held like an auto repeat, then pressed a second time.
+    for (key, state) in [
+        (control, ElementState::Pressed),
+        (f12, ElementState::Pressed),
+        (f12, ElementState::Pressed),
+        (f12, ElementState::Pressed),
+        (f12, ElementState::Released),
+        (f12, ElementState::Pressed),
+        (f12, ElementState::Released),
+        (control, ElementState::Released),
+    ] {
+        window.inject_key(key, state).unwrap();
+    }
+
+    // The key without the modifiers isn't the hotkey.
+    window.inject_key(f12, ElementState::Pressed).unwrap();
+    window.inject_key(f12, ElementState::Released).unwrap();
+
+    let mut hotkeys = 0;
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline {
+        event_loop.pump_events(Some(Duration::from_millis(50)), |event, _| {
+            if let Event::DeviceEvent {
+                event: DeviceEvent::GlobalHotkey(id),
+                ..
+            } = event
+            {
+                assert_eq!(id, hotkey);
+                hotkeys += 1;
+            }
+        });
+    }
+
+    assert_eq!(hotkeys, 2);
+
+    event_loop.unregister_global_hotkey(hotkey);
+}